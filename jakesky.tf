@@ -15,6 +15,11 @@ variable "jakesky_latitude" {}
 
 variable "jakesky_longitude" {}
 
+variable "jakesky_provider" {
+  type    = string
+  default = "openweather"
+}
+
 variable "aws_region" {
   type    = string
   default = "us-east-1"
@@ -92,6 +97,7 @@ resource "aws_lambda_function" "jakesky" {
       JAKESKY_API_KEY   = var.jakesky_api_key
       JAKESKY_LATITUDE  = var.jakesky_latitude
       JAKESKY_LONGITUDE = var.jakesky_longitude
+      JAKESKY_PROVIDER  = var.jakesky_provider
     }
   }
 }
@@ -0,0 +1,46 @@
+#!/bin/sh
+# Replaces the sample data/places.csv and data/zips.csv with GeoNames data
+# (https://www.geonames.org/), which is licensed under CC BY 4.0:
+#
+# - places.csv: every US city with at least 1,000 people (cities1000), keeping
+#   the most populous where a state has more than one of the same name.
+# - zips.csv: the centroid of every US ZIP code (the US postal code export).
+#
+# Names containing commas are skipped, as the tables aren't quoted.
+
+set -eu
+
+cd "$(dirname "$0")/.."
+
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+tab=$(printf '\t')
+
+curl -fsSL -o "$tmp/cities1000.zip" https://download.geonames.org/export/dump/cities1000.zip
+curl -fsSL -o "$tmp/US.zip" https://download.geonames.org/export/zip/US.zip
+unzip -q -d "$tmp" "$tmp/cities1000.zip" cities1000.txt
+unzip -q -d "$tmp" "$tmp/US.zip" US.txt
+
+# cities1000.txt: name is field 2, latitude 5, longitude 6, country 9, state 11
+# and population 15.
+{
+    echo "name,state,latitude,longitude"
+    awk -F "$tab" '$9 == "US" && $2 !~ /,/ && length($11) == 2 {
+        printf "%d\t%s\t%s\t%.4f\t%.4f\n", $15, $2, $11, $5, $6
+    }' "$tmp/cities1000.txt" |
+        sort -t "$tab" -k1,1nr |
+        awk -F "$tab" '!seen[$2 FS $3]++ { print $2 "," $3 "," $4 "," $5 }' |
+        LC_ALL=C sort -t, -k2,2 -k1,1
+} >data/places.csv
+
+# US.txt: ZIP code is field 2, name 3, state 5, latitude 10 and longitude 11.
+# Military ZIP codes have no coordinates.
+{
+    echo "zip,name,state,latitude,longitude"
+    awk -F "$tab" '$3 !~ /,/ && length($5) == 2 && $10 != "" {
+        printf "%s,%s,%s,%.4f,%.4f\n", $2, $3, $5, $10, $11
+    }' "$tmp/US.txt" |
+        LC_ALL=C sort
+} >data/zips.csv
+
+wc -l data/places.csv data/zips.csv
@@ -0,0 +1,258 @@
+//! A single sentence about what to take on the way out the door, e.g. "Bring
+//! an umbrella for the afternoon."
+
+use super::rain::is_wet;
+use super::{SpeechOptions, relative_day};
+use crate::weather::Weather;
+use chrono::{DateTime, Timelike};
+use chrono_tz::Tz;
+
+/// Default [`SpeechOptions::umbrella_threshold`].
+pub const DEFAULT_UMBRELLA_THRESHOLD: f64 = 0.5;
+
+/// Default [`SpeechOptions::jacket_threshold`], in degrees Fahrenheit.
+pub const DEFAULT_JACKET_THRESHOLD: f64 = 55.0;
+
+/// How far above the jacket threshold it can feel and still call for a
+/// jacket when it's windy, in degrees Fahrenheit.
+const WINDY_JACKET_MARGIN: f64 = 10.0;
+
+/// Advice for the first of `hours` that calls for an umbrella or a jacket,
+/// with the time of day given relative to `now`, or `None` if neither is
+/// needed.
+pub(super) fn advise(
+    hours: &[Weather],
+    now: &DateTime<Tz>,
+    options: &SpeechOptions,
+) -> Option<String> {
+    let umbrella = hours.iter().find(|w| is_wet(w, options.umbrella_threshold));
+    let jacket = hours.iter().find(|w| needs_jacket(w, options));
+
+    match (umbrella, jacket) {
+        (Some(umbrella), Some(jacket)) => Some(format!(
+            "Bring an umbrella for {}, and you'll want a jacket {}.",
+            umbrella_period(&umbrella.timestamp, now),
+            jacket_period(&jacket.timestamp, now)
+        )),
+        (Some(umbrella), None) => Some(format!(
+            "Bring an umbrella for {}.",
+            umbrella_period(&umbrella.timestamp, now)
+        )),
+        (None, Some(jacket)) => Some(format!(
+            "You'll want a jacket {}.",
+            jacket_period(&jacket.timestamp, now)
+        )),
+        (None, None) => None,
+    }
+}
+
+/// Whether it feels colder than the jacket threshold, or nearly that cold
+/// and windy.
+fn needs_jacket(weather: &Weather, options: &SpeechOptions) -> bool {
+    let units = weather.units;
+    let feels_like = weather.apparent_temp.unwrap_or(weather.temp);
+    let windy = weather
+        .wind_speed
+        .is_some_and(|speed| speed >= units.speed_from_mph(options.wind_threshold));
+
+    feels_like < units.temp_from_fahrenheit(options.jacket_threshold)
+        || (windy
+            && feels_like
+                < units.temp_from_fahrenheit(options.jacket_threshold + WINDY_JACKET_MARGIN))
+}
+
+fn part_of_day(timestamp: &DateTime<Tz>) -> &'static str {
+    match timestamp.hour() {
+        5..=11 => "morning",
+        12..=16 => "afternoon",
+        17..=20 => "evening",
+        _ => "night",
+    }
+}
+
+/// E.g. "the afternoon", "tonight" or "tomorrow morning".
+fn umbrella_period(timestamp: &DateTime<Tz>, now: &DateTime<Tz>) -> String {
+    match (
+        relative_day(timestamp, now).as_str(),
+        part_of_day(timestamp),
+    ) {
+        ("today", "night") => "tonight".to_string(),
+        ("today", part) => format!("the {part}"),
+        (day, part) => format!("{day} {part}"),
+    }
+}
+
+/// E.g. "this afternoon", "tonight" or "tomorrow morning".
+fn jacket_period(timestamp: &DateTime<Tz>, now: &DateTime<Tz>) -> String {
+    match (
+        relative_day(timestamp, now).as_str(),
+        part_of_day(timestamp),
+    ) {
+        ("today", "night") => "tonight".to_string(),
+        ("today", part) => format!("this {part}"),
+        (day, part) => format!("{day} {part}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::weather::Units;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Tz> {
+        Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 7, 0, 0)
+            .unwrap()
+    }
+
+    fn hour(hours: i64) -> Weather {
+        Weather {
+            timestamp: now() + Duration::hours(hours),
+            ..Weather::test(Some("Clear"))
+        }
+    }
+
+    #[test]
+    fn test_no_advice() {
+        let hours = [hour(0), hour(5), hour(11)];
+
+        assert_eq!(None, advise(&hours, &now(), &SpeechOptions::default()));
+    }
+
+    #[test]
+    fn test_umbrella() {
+        let hours = [
+            hour(0),
+            Weather {
+                precip_probability: Some(0.4),
+                ..hour(1)
+            },
+            Weather {
+                precip_probability: Some(0.7),
+                ..hour(6)
+            },
+        ];
+
+        assert_eq!(
+            Some("Bring an umbrella for the afternoon.".to_string()),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+
+        let options = SpeechOptions {
+            umbrella_threshold: 0.3,
+            ..SpeechOptions::default()
+        };
+        assert_eq!(
+            Some("Bring an umbrella for the morning.".to_string()),
+            advise(&hours, &now(), &options)
+        );
+    }
+
+    #[test]
+    fn test_umbrella_from_summary() {
+        let hours = [Weather {
+            summary: "Light Rain".to_string(),
+            ..hour(14)
+        }];
+
+        assert_eq!(
+            Some("Bring an umbrella for tonight.".to_string()),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_jacket() {
+        let hours = [
+            hour(0),
+            Weather {
+                temp: 60.0,
+                apparent_temp: Some(50.0),
+                ..hour(11)
+            },
+        ];
+
+        assert_eq!(
+            Some("You'll want a jacket this evening.".to_string()),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+
+        let options = SpeechOptions {
+            jacket_threshold: 45.0,
+            ..SpeechOptions::default()
+        };
+        assert_eq!(None, advise(&hours, &now(), &options));
+    }
+
+    #[test]
+    fn test_jacket_when_windy() {
+        let hours = [Weather {
+            temp: 60.0,
+            wind_speed: Some(25.0),
+            ..hour(26)
+        }];
+
+        assert_eq!(
+            Some("You'll want a jacket tomorrow morning.".to_string()),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_jacket_in_metric() {
+        let hours = [Weather {
+            units: Units::Metric,
+            temp: 10.0,
+            ..hour(0)
+        }];
+
+        assert_eq!(
+            Some("You'll want a jacket this morning.".to_string()),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_jacket_with_metric_threshold() {
+        let options = SpeechOptions {
+            jacket_threshold: Units::Metric.temp_to_fahrenheit(12.0),
+            ..SpeechOptions::default()
+        };
+        let at = |temp| {
+            [Weather {
+                units: Units::Metric,
+                temp,
+                ..hour(0)
+            }]
+        };
+
+        assert_eq!(
+            Some("You'll want a jacket this morning.".to_string()),
+            advise(&at(11.0), &now(), &options)
+        );
+        assert_eq!(None, advise(&at(13.0), &now(), &options));
+    }
+
+    #[test]
+    fn test_umbrella_and_jacket() {
+        let hours = [
+            Weather {
+                precip_probability: Some(0.8),
+                ..hour(6)
+            },
+            Weather {
+                temp: 48.0,
+                ..hour(11)
+            },
+        ];
+
+        assert_eq!(
+            Some(
+                "Bring an umbrella for the afternoon, and you'll want a jacket this evening."
+                    .to_string()
+            ),
+            advise(&hours, &now(), &SpeechOptions::default())
+        );
+    }
+}
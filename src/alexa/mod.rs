@@ -0,0 +1,2027 @@
+use crate::ai::AlertSummarize;
+use crate::alert_summary::{extract_phenomenon, is_vague_event};
+use crate::ssml;
+use crate::weather::{DailyWeather, Units, Weather, WeatherAlert, WeatherReport, whole_degrees};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use log::{info, warn};
+use rain::Spell;
+use serde_json::{Value, json};
+
+pub mod advice;
+mod rain;
+pub mod request;
+pub mod verify;
+
+/// How to refer to an alert when reading it aloud.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AlertSubject {
+    /// Use the event name as a noun (e.g. "winter storm warning"). Spoken with
+    /// an article and "is": "There is a winter storm warning ...".
+    Event(String),
+    /// Use a concrete phenomenon extracted from the description (e.g.
+    /// "areas of fog"). Spoken without an article and with "will be":
+    /// "There will be areas of fog ...".
+    Phenomenon(String),
+}
+
+async fn alert_subject<S: AlertSummarize>(
+    alert: &WeatherAlert,
+    summarizer: Option<&S>,
+) -> AlertSubject {
+    let event_lower = alert.event.to_lowercase();
+
+    if !is_vague_event(&alert.event) {
+        return AlertSubject::Event(event_lower);
+    }
+
+    if let Some(phenomenon) = extract_phenomenon(&alert.description) {
+        return AlertSubject::Phenomenon(phenomenon);
+    }
+
+    if let Some(summarizer) = summarizer {
+        match summarizer
+            .summarize_alert(&alert.event, &alert.description)
+            .await
+        {
+            Ok(phrase) => return AlertSubject::Phenomenon(phrase),
+            Err(e) => warn!("Bedrock summarization failed for {:?}: {e}", alert.event),
+        }
+    }
+
+    AlertSubject::Event(event_lower)
+}
+
+/// Default [`SpeechOptions::precip_threshold`].
+pub const DEFAULT_PRECIP_THRESHOLD: f64 = 0.3;
+
+/// Default [`SpeechOptions::wind_threshold`], in miles per hour.
+pub const DEFAULT_WIND_THRESHOLD: f64 = 20.0;
+
+/// Default [`SpeechOptions::gust_threshold`], in miles per hour.
+pub const DEFAULT_GUST_THRESHOLD: f64 = 30.0;
+
+/// Preferences for how the forecast is read aloud.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechOptions {
+    /// Mention the chance of precipitation once it reaches this probability,
+    /// from 0.0 to 1.0.
+    pub precip_threshold: f64,
+    /// Call it windy once the sustained wind reaches this speed, in miles per
+    /// hour whatever units the forecast is in.
+    pub wind_threshold: f64,
+    /// Mention gusts once they reach this speed, in miles per hour.
+    pub gust_threshold: f64,
+    /// Suggest an umbrella once the chance of precipitation reaches this
+    /// probability, from 0.0 to 1.0.
+    pub umbrella_threshold: f64,
+    /// Suggest a jacket once it feels colder than this, in degrees Fahrenheit
+    /// whatever units the forecast is in; convert settings given in the
+    /// forecast's units with [`Units::temp_to_fahrenheit`].
+    pub jacket_threshold: f64,
+}
+
+impl Default for SpeechOptions {
+    fn default() -> Self {
+        Self {
+            precip_threshold: DEFAULT_PRECIP_THRESHOLD,
+            wind_threshold: DEFAULT_WIND_THRESHOLD,
+            gust_threshold: DEFAULT_GUST_THRESHOLD,
+            umbrella_threshold: advice::DEFAULT_UMBRELLA_THRESHOLD,
+            jacket_threshold: advice::DEFAULT_JACKET_THRESHOLD,
+        }
+    }
+}
+
+/// A forecast ready to be read aloud, in whichever form the caller needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Speech {
+    pub sentences: Vec<String>,
+    /// Index of the sentence about alerts, if any, which is preceded by a
+    /// pause.
+    alerts_at: Option<usize>,
+}
+
+impl Speech {
+    /// Alerts, when there are any, are always the last sentence.
+    fn new(sentences: Vec<String>, has_alerts: bool) -> Self {
+        let alerts_at = has_alerts.then(|| sentences.len() - 1);
+
+        Self {
+            sentences,
+            alerts_at,
+        }
+    }
+
+    /// The sentences as plain text.
+    pub fn text(&self) -> String {
+        self.sentences.join(" ")
+    }
+
+    pub fn ssml(&self) -> String {
+        ssml::render(&self.sentences, self.alerts_at)
+    }
+
+    /// The full Alexa response, which ends the session.
+    pub fn response(&self) -> Value {
+        speech_response(self.ssml(), None, true)
+    }
+}
+
+pub async fn forecast<S: AlertSummarize>(
+    report: WeatherReport,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Value> {
+    forecast_on(report, None, summarizer, options).await
+}
+
+/// The speech for [`forecast`], without logging it or building a response.
+pub async fn speech<S: AlertSummarize>(
+    report: WeatherReport,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Speech> {
+    speech_on(report, None, summarizer, options).await
+}
+
+/// Reads the forecast for `date`, from a report built by
+/// [`WeatherProvider::get_weather_for_date`](crate::weather::WeatherProvider::get_weather_for_date).
+/// Today gets the usual forecast, and earlier dates are turned away, as
+/// "today" depends on the location's timezone, which only the report knows.
+pub async fn forecast_for_date<S: AlertSummarize>(
+    report: WeatherReport,
+    date: NaiveDate,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Value> {
+    let today = report.weather.first().map(|w| w.timestamp.date_naive());
+
+    match today {
+        Some(today) if date < today => {
+            info!("Not reading past date: {date}");
+            Ok(past_date())
+        }
+        Some(today) if date == today => forecast_on(report, None, summarizer, options).await,
+        _ => forecast_on(report, Some(date), summarizer, options).await,
+    }
+}
+
+async fn forecast_on<S: AlertSummarize>(
+    report: WeatherReport,
+    date: Option<NaiveDate>,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Value> {
+    let speech = speech_on(report, date, summarizer, options).await?;
+
+    info!(r#"Forecast: "{}""#, speech.text());
+
+    Ok(speech.response())
+}
+
+async fn speech_on<S: AlertSummarize>(
+    report: WeatherReport,
+    date: Option<NaiveDate>,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Speech> {
+    let has_alerts = !report.alerts.is_empty();
+    let sentences = to_forecast(report, date, summarizer, options).await?;
+
+    Ok(Speech::new(sentences, has_alerts))
+}
+
+/// The speech for a commute: the conditions when leaving at `depart` and
+/// when heading home at `return_hour`, from a report built by
+/// [`WeatherProvider::get_weather_along`](crate::weather::WeatherProvider::get_weather_along).
+pub async fn commute_speech<S: AlertSummarize>(
+    report: WeatherReport,
+    depart: u32,
+    return_hour: u32,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Speech> {
+    let has_alerts = !report.alerts.is_empty();
+    let sentences = to_commute(report, depart, return_hour, summarizer, options).await?;
+
+    Ok(Speech::new(sentences, has_alerts))
+}
+
+/// Reads the hourly forecast nearest the next `time` of day, e.g. in answer
+/// to "will it rain at 3pm?".
+pub fn forecast_for_time(
+    report: WeatherReport,
+    time: NaiveTime,
+    options: &SpeechOptions,
+) -> Result<Value> {
+    let sentence = to_forecast_for_time(report, time, options)?;
+
+    info!(r#"Forecast: "{sentence}""#);
+
+    Ok(speech_response(ssml::render(&[sentence], None), None, true))
+}
+
+/// Response to `AMAZON.HelpIntent`, which keeps the session open so the user
+/// can ask for the forecast straight away.
+pub fn help() -> Value {
+    let sentences = [
+        "JakeSky reads you the current conditions, the forecast for the rest of the day, and any weather alerts.".to_string(),
+        "Would you like the forecast?".to_string(),
+    ];
+
+    speech_response(
+        ssml::render(&sentences, None),
+        Some(ssml::render(&sentences[1..], None)),
+        false,
+    )
+}
+
+/// Response to `AMAZON.StopIntent` and `AMAZON.CancelIntent`.
+pub fn goodbye() -> Value {
+    speech_response(ssml::render(&["Goodbye.".to_string()], None), None, true)
+}
+
+/// Response to a request for the forecast on a day that's already passed.
+fn past_date() -> Value {
+    let sentences = [
+        "I can only tell you the forecast, not the weather on days that have passed.".to_string(),
+    ];
+
+    speech_response(ssml::render(&sentences, None), None, true)
+}
+
+/// Response without speech, e.g. to a `SessionEndedRequest`, which Alexa
+/// doesn't allow a spoken reply to.
+pub fn empty() -> Value {
+    json!({
+        "version": "1.0",
+        "response": {}
+    })
+}
+
+fn speech_response(ssml: String, reprompt: Option<String>, should_end_session: bool) -> Value {
+    let mut response = json!({
+        "outputSpeech": {
+            "type": "SSML",
+            "ssml": ssml,
+        },
+        "shouldEndSession": should_end_session,
+    });
+
+    if let Some(reprompt) = reprompt {
+        response["reprompt"] = json!({
+            "outputSpeech": {
+                "type": "SSML",
+                "ssml": reprompt,
+            }
+        });
+    }
+
+    json!({
+        "version": "1.0",
+        "response": response,
+    })
+}
+
+/// Sentences for the rest of today or, given a later `date`, for that day
+/// alone. The report's first entry is always the current conditions, which
+/// are only read aloud for today.
+async fn to_forecast<S: AlertSummarize>(
+    report: WeatherReport,
+    date: Option<NaiveDate>,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Vec<String>> {
+    let WeatherReport {
+        weather,
+        hourly,
+        daily,
+        alerts,
+    } = report;
+
+    let [current, upcoming @ ..] = weather.as_slice() else {
+        return Err(anyhow!("Weather cannot be empty"));
+    };
+
+    let mut forecast = Vec::with_capacity(weather.len() + 2);
+
+    // Describe the day the upcoming hours fall on, which is tomorrow once
+    // today's hours have passed.
+    let forecast_date =
+        date.unwrap_or_else(|| upcoming.first().unwrap_or(current).timestamp.date_naive());
+    let outlook = daily.iter().find(|d| d.date == forecast_date);
+    if let Some(day) = outlook {
+        forecast.push(format_high_and_low(day, current));
+    }
+
+    // Once the rain's start and stop have been narrated, the hours within it
+    // needn't repeat the chance of rain.
+    let spell = Spell::find(current, &hourly, forecast_date, options.precip_threshold);
+    let within_spell = SpeechOptions {
+        precip_threshold: f64::INFINITY,
+        ..options.clone()
+    };
+    let options_at = |w: &Weather| match &spell {
+        Some(spell) if spell.covers(&w.timestamp) => &within_spell,
+        _ => options,
+    };
+
+    if date.is_none() {
+        forecast.push(format!(
+            "It's currently {}.",
+            speakable_weather(current, options_at(current))
+        ));
+    } else if upcoming.is_empty() {
+        // Beyond the provider's hourly data, so describe the day as a whole.
+        let label = relative_day_from_date(forecast_date, current.timestamp.date_naive());
+        forecast.push(match outlook {
+            Some(day) => format!("{} will be {}.", capitalize(&label), day.summary),
+            None => format!("I don't have a forecast for {label} yet."),
+        });
+    }
+
+    if let Some(spell) = &spell {
+        forecast.push(spell.speakable(&current.timestamp));
+    }
+
+    // Lead with the day label whenever the hours move on to a later day, e.g.
+    // "Tomorrow at 8am" once today's hours have passed.
+    let mut day = current.timestamp.date_naive();
+
+    for (index, w) in upcoming.iter().enumerate() {
+        let is_last = index + 1 == upcoming.len();
+
+        let lead = if w.timestamp.date_naive() != day {
+            day = w.timestamp.date_naive();
+            format!(
+                "{} at",
+                capitalize(&relative_day(&w.timestamp, &current.timestamp))
+            )
+        } else if is_last && index > 0 {
+            "And at".to_string()
+        } else {
+            "At".to_string()
+        };
+
+        forecast.push(format!(
+            "{} {}{} it will be {}.",
+            lead,
+            speakable_timestamp(&w.timestamp),
+            if is_last { "" } else { "," },
+            speakable_weather(w, options_at(w))
+        ));
+    }
+
+    // The current conditions only matter for today.
+    let hours = if date.is_none() {
+        &weather[..]
+    } else {
+        upcoming
+    };
+    if let Some(advice) = advice::advise(hours, &current.timestamp, options) {
+        forecast.push(advice);
+    }
+
+    if !alerts.is_empty() {
+        forecast.push(format_alerts(&alerts, summarizer).await);
+    }
+
+    Ok(forecast)
+}
+
+async fn to_commute<S: AlertSummarize>(
+    report: WeatherReport,
+    depart: u32,
+    return_hour: u32,
+    summarizer: Option<&S>,
+    options: &SpeechOptions,
+) -> Result<Vec<String>> {
+    let WeatherReport {
+        weather,
+        hourly,
+        alerts,
+        ..
+    } = report;
+
+    let [current, upcoming @ ..] = weather.as_slice() else {
+        return Err(anyhow!("Weather cannot be empty"));
+    };
+
+    // Hours less than an hour away are left to the current conditions, so
+    // read an imminent departure from the hourly series, which is for home,
+    // or failing that from the current conditions.
+    let now = current.timestamp;
+    let leaving_soon = (now.hour() == depart || now.hour() + 1 == depart).then(|| {
+        hourly
+            .iter()
+            .find(|w| {
+                w.timestamp > now
+                    && w.timestamp.date_naive() == now.date_naive()
+                    && w.timestamp.hour() == depart
+            })
+            .unwrap_or(current)
+    });
+
+    let mut commute = Vec::with_capacity(3);
+
+    for w in leaving_soon.into_iter().chain(upcoming) {
+        let leg = match w.timestamp.hour() {
+            hour if hour == depart => "Leaving",
+            hour if hour == return_hour => "Heading home",
+            _ => continue,
+        };
+        let day = if w.timestamp.date_naive() == current.timestamp.date_naive() {
+            String::new()
+        } else {
+            format!(" {}", relative_day(&w.timestamp, &current.timestamp))
+        };
+
+        commute.push(format!(
+            "{leg}{day} at {}, it'll be {}.",
+            speakable_timestamp(&w.timestamp),
+            speakable_weather(w, options)
+        ));
+    }
+
+    if commute.is_empty() {
+        commute.push("I don't have a forecast for your commute yet.".to_string());
+    }
+
+    if !alerts.is_empty() {
+        commute.push(format_alerts(&alerts, summarizer).await);
+    }
+
+    Ok(commute)
+}
+
+fn to_forecast_for_time(
+    report: WeatherReport,
+    time: NaiveTime,
+    options: &SpeechOptions,
+) -> Result<String> {
+    let current = report
+        .weather
+        .first()
+        .ok_or_else(|| anyhow!("Weather cannot be empty"))?;
+    let now = current.timestamp;
+
+    // The next time the clock reads `time`, which is tomorrow once it's passed.
+    let mut target = now.date_naive().and_time(time);
+    if target <= now.naive_local() {
+        target += Duration::days(1);
+    }
+    let target = now
+        .timezone()
+        .from_local_datetime(&target)
+        .earliest()
+        .ok_or_else(|| anyhow!("{target} doesn't exist in {}", now.timezone()))?;
+
+    let nearest = report
+        .hourly
+        .iter()
+        .min_by_key(|w| (w.timestamp - target).num_seconds().abs())
+        .filter(|w| (w.timestamp - target).num_minutes().abs() <= 60);
+
+    let Some(weather) = nearest else {
+        return Ok(format!(
+            "I don't have a forecast for {} yet.",
+            speakable_moment(&target, &now)
+        ));
+    };
+
+    let lead = if weather.timestamp.date_naive() == now.date_naive() {
+        "At".to_string()
+    } else {
+        format!("{} at", capitalize(&relative_day(&weather.timestamp, &now)))
+    };
+
+    Ok(format!(
+        "{} {}, it will be {}.",
+        lead,
+        speakable_timestamp(&weather.timestamp),
+        speakable_weather(weather, options)
+    ))
+}
+
+fn speakable_timestamp(timestamp: &DateTime<Tz>) -> String {
+    match timestamp.hour() {
+        0 => "midnight".to_string(),
+        12 => "noon".to_string(),
+        _ => timestamp.format("%-I%P").to_string(),
+    }
+}
+
+fn speakable_weather(weather: &Weather, options: &SpeechOptions) -> String {
+    let temp = whole_degrees(weather.apparent_temp.unwrap_or(weather.temp));
+    let mut spoken = format_temp_and_summary(temp, weather.units, &weather.summary);
+
+    if let Some(probability) = weather.precip_probability
+        && probability > 0.0
+        && probability >= options.precip_threshold
+    {
+        spoken.push_str(&format_precip_chance(probability, &weather.summary));
+    }
+
+    if let Some(wind) = format_wind(weather, options) {
+        spoken.push_str(", ");
+        spoken.push_str(&wind);
+    }
+
+    spoken
+}
+
+/// E.g. "windy from the northwest, gusts to 35", or `None` when the wind
+/// isn't worth mentioning. Gusts are read in the forecast's own units.
+fn format_wind(weather: &Weather, options: &SpeechOptions) -> Option<String> {
+    let wind_threshold = weather.units.speed_from_mph(options.wind_threshold);
+    let gust_threshold = weather.units.speed_from_mph(options.gust_threshold);
+
+    let windy = weather
+        .wind_speed
+        .is_some_and(|speed| speed >= wind_threshold);
+    let gusts = weather
+        .wind_gust
+        .filter(|&gust| gust >= gust_threshold)
+        .map(|gust| format!("gusts to {}", gust.round() as i64));
+
+    let windy = windy.then(|| match weather.wind_bearing {
+        Some(bearing) => format!("windy from the {}", compass_direction(bearing)),
+        None => "windy".to_string(),
+    });
+
+    match (windy, gusts) {
+        (Some(windy), Some(gusts)) => Some(format!("{windy}, {gusts}")),
+        (Some(windy), None) => Some(windy),
+        (None, Some(gusts)) => Some(format!("with {gusts}")),
+        (None, None) => None,
+    }
+}
+
+/// Nearest of the eight compass points for a bearing in degrees.
+fn compass_direction(bearing: f64) -> &'static str {
+    const POINTS: [&str; 8] = [
+        "north",
+        "northeast",
+        "east",
+        "southeast",
+        "south",
+        "southwest",
+        "west",
+        "northwest",
+    ];
+
+    let index = (bearing.rem_euclid(360.0) / 45.0).round() as usize % POINTS.len();
+    POINTS[index]
+}
+
+fn format_precip_chance(probability: f64, summary: &str) -> String {
+    let percent = (probability * 100.0).round() as i64;
+
+    format!(
+        " with {} {percent} percent chance of {}",
+        article_for(percent),
+        precip_kind(summary)
+    )
+}
+
+/// "snow" if the summary mentions it, otherwise "rain".
+fn precip_kind(summary: &str) -> &'static str {
+    if summary.to_lowercase().contains("snow") {
+        "snow"
+    } else {
+        "rain"
+    }
+}
+
+/// "an" for numbers read aloud with a leading vowel sound: 8, 11, 18, 80-89.
+fn article_for(n: i64) -> &'static str {
+    if n == 8 || n == 11 || n == 18 || (80..=89).contains(&n) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+/// E.g. "72" or "5 below" in Fahrenheit, "22 degrees Celsius" or "5 degrees
+/// Celsius below zero" in Celsius.
+fn format_temp(temp: i64, units: Units) -> String {
+    let below = temp < 0;
+
+    match units {
+        Units::Imperial => format!("{}{}", temp.abs(), if below { " below" } else { "" }),
+        Units::Metric => format!(
+            "{} degrees Celsius{}",
+            temp.abs(),
+            if below { " below zero" } else { "" }
+        ),
+    }
+}
+
+fn format_temp_and_summary(temp: i64, units: Units, summary: &str) -> String {
+    format!("{} and {}", format_temp(temp, units), summary)
+}
+
+/// E.g. "Today's high is 78, low of 61."
+fn format_high_and_low(day: &DailyWeather, current: &Weather) -> String {
+    let label = relative_day_from_date(day.date, current.timestamp.date_naive());
+
+    format!(
+        "{}'s high is {}, low of {}.",
+        capitalize(&label),
+        format_temp(whole_degrees(day.high), day.units),
+        format_temp(whole_degrees(day.low), day.units)
+    )
+}
+
+async fn format_alerts<S: AlertSummarize>(
+    alerts: &[WeatherAlert],
+    summarizer: Option<&S>,
+) -> String {
+    let count = alerts.len();
+    let mut parts = Vec::new();
+
+    // Announce first 2 alerts, preferring a concrete phenomenon over the
+    // generic event name when the event is vague (e.g. "Special Weather
+    // Statement").
+    for (index, alert) in alerts.iter().take(2).enumerate() {
+        let time_range = format_alert_timerange(&alert.start, &alert.end);
+        let subject = alert_subject(alert, summarizer).await;
+
+        let phrase = match (index, &subject) {
+            (0, AlertSubject::Event(name)) => format!("There is a {} {}", name, time_range),
+            (0, AlertSubject::Phenomenon(p)) => format!("There will be {} {}", p, time_range),
+            (_, AlertSubject::Event(name)) => format!("And a {} {}", name, time_range),
+            (_, AlertSubject::Phenomenon(p)) => format!("And {} {}", p, time_range),
+        };
+        parts.push(phrase);
+    }
+
+    if count > 2 {
+        let remaining = count - 2;
+        let plural = if remaining == 1 { "alert" } else { "alerts" };
+        parts.push(format!("And {} more {}", remaining, plural));
+    }
+
+    parts.join(". ") + "."
+}
+
+fn format_alert_timerange(start: &DateTime<Tz>, end: &DateTime<Tz>) -> String {
+    // Format as "from 7am tomorrow through 8pm Monday" or "from midnight through 11am today"
+    let start_time = speakable_timestamp(start);
+    let end_time = speakable_timestamp(end);
+
+    let now = start.timezone().from_utc_datetime(&Utc::now().naive_utc());
+    let start_day = relative_day(start, &now);
+    let end_day = relative_day(end, &now);
+
+    // Omit start time if alert started in the past
+    if start < &now {
+        format!("until {}", speakable_moment(end, &now))
+    } else if start_day == end_day {
+        format!("from {} through {} {}", start_time, end_time, end_day)
+    } else {
+        format!(
+            "from {} through {}",
+            speakable_moment(start, &now),
+            speakable_moment(end, &now)
+        )
+    }
+}
+
+/// Spoken "time day" for a moment, e.g. "8am tomorrow" or "midnight tonight".
+///
+/// Midnight (00:00) is reframed as belonging to the previous evening: a
+/// listener hears "midnight tonight" for the upcoming midnight, not "midnight
+/// tomorrow", since the instant caps today rather than opening tomorrow.
+fn speakable_moment(dt: &DateTime<Tz>, now: &DateTime<Tz>) -> String {
+    if dt.hour() == 0 {
+        return format!("midnight {}", night_label(dt, now));
+    }
+    format!("{} {}", speakable_timestamp(dt), relative_day(dt, now))
+}
+
+/// Day label for a midnight, framed as the night it caps (the previous day).
+fn night_label(midnight: &DateTime<Tz>, now: &DateTime<Tz>) -> String {
+    let evening_date = midnight.date_naive() - Duration::days(1);
+    match relative_day_from_date(evening_date, now.date_naive()).as_str() {
+        "today" => "tonight".to_string(),
+        "yesterday" => "last night".to_string(),
+        "tomorrow" => "tomorrow night".to_string(),
+        weekday => format!("{} night", weekday),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn relative_day(dt: &DateTime<Tz>, now: &DateTime<Tz>) -> String {
+    relative_day_from_date(dt.date_naive(), now.date_naive())
+}
+
+/// Day label ("today", "tomorrow", "yesterday", or the weekday name) for a
+/// calendar date relative to another, computed purely on `NaiveDate` so it's
+/// unaffected by DST transitions in either date's original timezone.
+fn relative_day_from_date(date: NaiveDate, now: NaiveDate) -> String {
+    let days_diff = date.signed_duration_since(now).num_days();
+    match days_diff {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        _ => date.format("%A").to_string(), // Day of week
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Stub summarizer for exercising the LLM fallback path without hitting
+    /// AWS. `phrase: Some(..)` produces a successful response; `None`
+    /// produces an error so the caller's fallback-to-event-name can be
+    /// tested.
+    struct StubSummarizer {
+        phrase: Option<String>,
+    }
+
+    impl AlertSummarize for StubSummarizer {
+        async fn summarize_alert(&self, _event: &str, _description: &str) -> Result<String> {
+            self.phrase
+                .clone()
+                .ok_or_else(|| anyhow!("stub summarizer failure"))
+        }
+    }
+
+    /// Typed `None` for tests that don't need a summarizer, so type
+    /// inference picks up the generic parameter.
+    const NO_SUMMARIZER: Option<&StubSummarizer> = None;
+
+    fn report(weather: Vec<Weather>, alerts: Vec<WeatherAlert>) -> WeatherReport {
+        WeatherReport {
+            weather,
+            hourly: Vec::new(),
+            daily: Vec::new(),
+            alerts,
+        }
+    }
+
+    #[test]
+    fn test_speakable_weather() {
+        assert!(format_temp_and_summary(72, Units::Imperial, "foo").starts_with("72 and"));
+        assert!(format_temp_and_summary(-72, Units::Imperial, "foo").starts_with("72 below and"));
+    }
+
+    #[test]
+    fn test_speakable_weather_celsius() {
+        assert_eq!(
+            "22 degrees Celsius and foo",
+            format_temp_and_summary(22, Units::Metric, "foo")
+        );
+        assert_eq!(
+            "5 degrees Celsius below zero and foo",
+            format_temp_and_summary(-5, Units::Metric, "foo")
+        );
+    }
+
+    #[test]
+    fn test_speakable_weather_wind_in_kilometers() {
+        let mut weather = Weather::test(Some("Cloudy"));
+        weather.units = Units::Metric;
+        weather.temp = 20.0;
+
+        // 25 km/h is under the 20 mph default, 40 km/h is just over it.
+        weather.wind_speed = Some(25.0);
+        assert_eq!(
+            "20 degrees Celsius and Cloudy",
+            speakable_weather(&weather, &SpeechOptions::default())
+        );
+
+        weather.wind_speed = Some(40.0);
+        weather.wind_gust = Some(56.0);
+        assert_eq!(
+            "20 degrees Celsius and Cloudy, windy, gusts to 56",
+            speakable_weather(&weather, &SpeechOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_speakable_weather_precip_chance() {
+        let options = SpeechOptions::default();
+        let mut weather = Weather::test(Some("Rain"));
+
+        weather.precip_probability = Some(0.6);
+        assert_eq!(
+            "72 and Rain with a 60 percent chance of rain",
+            speakable_weather(&weather, &options)
+        );
+
+        weather.precip_probability = Some(0.2);
+        assert_eq!("72 and Rain", speakable_weather(&weather, &options));
+
+        weather.precip_probability = Some(0.2);
+        let options = SpeechOptions {
+            precip_threshold: 0.2,
+            ..SpeechOptions::default()
+        };
+        assert_eq!(
+            "72 and Rain with a 20 percent chance of rain",
+            speakable_weather(&weather, &options)
+        );
+    }
+
+    #[test]
+    fn test_speakable_weather_chance_of_snow() {
+        let mut weather = Weather::test(Some("Light Snow"));
+        weather.precip_probability = Some(0.85);
+
+        assert_eq!(
+            "72 and Light Snow with an 85 percent chance of snow",
+            speakable_weather(&weather, &SpeechOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_speakable_weather_wind() {
+        let options = SpeechOptions::default();
+        let mut weather = Weather::test(Some("Cloudy"));
+
+        weather.wind_speed = Some(12.0);
+        weather.wind_gust = Some(18.0);
+        weather.wind_bearing = Some(300.0);
+        assert_eq!("72 and Cloudy", speakable_weather(&weather, &options));
+
+        weather.wind_speed = Some(24.0);
+        weather.wind_gust = Some(35.4);
+        assert_eq!(
+            "72 and Cloudy, windy from the northwest, gusts to 35",
+            speakable_weather(&weather, &options)
+        );
+
+        weather.wind_bearing = None;
+        weather.wind_gust = None;
+        assert_eq!(
+            "72 and Cloudy, windy",
+            speakable_weather(&weather, &options)
+        );
+
+        weather.wind_speed = Some(15.0);
+        weather.wind_gust = Some(31.0);
+        assert_eq!(
+            "72 and Cloudy, with gusts to 31",
+            speakable_weather(&weather, &options)
+        );
+    }
+
+    #[test]
+    fn test_compass_direction() {
+        assert_eq!("north", compass_direction(0.0));
+        assert_eq!("north", compass_direction(350.0));
+        assert_eq!("northeast", compass_direction(40.0));
+        assert_eq!("south", compass_direction(180.0));
+        assert_eq!("west", compass_direction(271.0));
+        assert_eq!("north", compass_direction(360.0));
+    }
+
+    #[test]
+    fn test_article_for() {
+        assert_eq!("a", article_for(60));
+        assert_eq!("a", article_for(100));
+        assert_eq!("an", article_for(8));
+        assert_eq!("an", article_for(11));
+        assert_eq!("an", article_for(18));
+        assert_eq!("an", article_for(80));
+        assert_eq!("a", article_for(90));
+    }
+
+    #[test]
+    fn test_speakable_weather_never_mentions_zero_chance() {
+        let mut weather = Weather::test(Some("Clear"));
+        weather.precip_probability = Some(0.0);
+        let options = SpeechOptions {
+            precip_threshold: 0.0,
+            ..SpeechOptions::default()
+        };
+
+        assert_eq!("72 and Clear", speakable_weather(&weather, &options));
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_empty() {
+        assert!(
+            to_forecast(
+                report(Vec::new(), Vec::new()),
+                None,
+                NO_SUMMARIZER,
+                &SpeechOptions::default()
+            )
+            .await
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_one_weather() -> Result<()> {
+        let weather = vec![Weather::test(Some("1"))];
+        let forecast = to_forecast(
+            report(weather, Vec::new()),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(1, forecast.len());
+        assert!(!forecast[0].contains("And"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_two_weather() -> Result<()> {
+        let weather = vec![Weather::test(Some("1")), Weather::test(Some("2"))];
+        let forecast = to_forecast(
+            report(weather, Vec::new()),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(!forecast[1].contains("And"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_multiple_weather() -> Result<()> {
+        let weather = vec![
+            Weather::test(Some("1")),
+            Weather::test(Some("2")),
+            Weather::test(Some("3")),
+        ];
+        let forecast = to_forecast(
+            report(weather, Vec::new()),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(3, forecast.len());
+        assert!(!forecast[1].contains("And"));
+        assert!(forecast[2].contains("And"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_tomorrow() -> Result<()> {
+        let at = |day, hour| Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, day, hour, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let weather = vec![at(15, 20), at(16, 8), at(16, 12), at(16, 18)];
+        let forecast = to_forecast(
+            report(weather, Vec::new()),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(
+            vec![
+                "It's currently 72 and sunny.",
+                "Tomorrow at 8am, it will be 72 and sunny.",
+                "At noon, it will be 72 and sunny.",
+                "And at 6pm it will be 72 and sunny.",
+            ],
+            forecast
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_only_tomorrow() -> Result<()> {
+        let at = |day, hour| Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, day, hour, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let weather = vec![at(15, 20), at(16, 8)];
+        let forecast = to_forecast(
+            report(weather, Vec::new()),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!("Tomorrow at 8am it will be 72 and sunny.", forecast[1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_opens_with_high_and_low() -> Result<()> {
+        let current = Weather::test(Some("sunny"));
+        let daily = vec![DailyWeather {
+            date: current.timestamp.date_naive(),
+            summary: "Sunny".to_string(),
+            units: Units::Imperial,
+            high: 78.4,
+            low: -3.0,
+        }];
+        let report = WeatherReport {
+            weather: vec![current],
+            hourly: Vec::new(),
+            daily,
+            alerts: Vec::new(),
+        };
+
+        let forecast = to_forecast(report, None, NO_SUMMARIZER, &SpeechOptions::default()).await?;
+
+        assert_eq!("Today's high is 78, low of 3 below.", forecast[0]);
+        assert_eq!("It's currently 72 and sunny.", forecast[1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_high_and_low_for_tomorrow() -> Result<()> {
+        let at = |day, hour| Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, day, hour, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let day = |date| DailyWeather {
+            date: NaiveDate::from_ymd_opt(2024, 1, date).unwrap(),
+            summary: "Sunny".to_string(),
+            units: Units::Imperial,
+            high: 40.0 + date as f64,
+            low: 30.0,
+        };
+        let report = WeatherReport {
+            weather: vec![at(15, 20), at(16, 8)],
+            hourly: Vec::new(),
+            daily: vec![day(15), day(16)],
+            alerts: Vec::new(),
+        };
+
+        let forecast = to_forecast(report, None, NO_SUMMARIZER, &SpeechOptions::default()).await?;
+
+        assert_eq!("Tomorrow's high is 56, low of 30.", forecast[0]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_for_date() -> Result<()> {
+        let at = |day, hour| Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, day, hour, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let saturday = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+        let report = WeatherReport {
+            weather: vec![at(17, 9), at(20, 10), at(20, 14)],
+            hourly: Vec::new(),
+            daily: vec![DailyWeather {
+                date: saturday,
+                summary: "Sunny".to_string(),
+                units: Units::Imperial,
+                high: 45.0,
+                low: 30.0,
+            }],
+            alerts: Vec::new(),
+        };
+
+        let forecast = to_forecast(
+            report,
+            Some(saturday),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(
+            vec![
+                "Saturday's high is 45, low of 30.",
+                "Saturday at 10am, it will be 72 and sunny.",
+                "And at 2pm it will be 72 and sunny.",
+            ],
+            forecast
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_for_date_beyond_hourly_data() -> Result<()> {
+        let current = Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let saturday = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+        let daily = vec![DailyWeather {
+            date: saturday,
+            summary: "Light Snow".to_string(),
+            units: Units::Imperial,
+            high: 33.0,
+            low: 25.0,
+        }];
+
+        let forecast = to_forecast(
+            WeatherReport {
+                weather: vec![current],
+                hourly: Vec::new(),
+                daily,
+                alerts: Vec::new(),
+            },
+            Some(saturday),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(
+            vec![
+                "Saturday's high is 33, low of 25.",
+                "Saturday will be Light Snow.",
+            ],
+            forecast
+        );
+
+        let current = Weather {
+            timestamp: Tz::America__New_York
+                .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+                .unwrap(),
+            ..Weather::test(Some("sunny"))
+        };
+        let forecast = to_forecast(
+            report(vec![current], Vec::new()),
+            NaiveDate::from_ymd_opt(2024, 1, 16),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(vec!["I don't have a forecast for tomorrow yet."], forecast);
+
+        Ok(())
+    }
+
+    fn report_at(now: DateTime<Tz>, hours: std::ops::Range<i64>) -> WeatherReport {
+        let at = |timestamp| Weather {
+            timestamp,
+            ..Weather::test(Some("Rain"))
+        };
+
+        WeatherReport {
+            weather: vec![at(now)],
+            hourly: hours.map(|h| at(now + Duration::hours(h))).collect(),
+            daily: Vec::new(),
+            alerts: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_to_commute() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 6, 0, 0)
+            .unwrap();
+        let at = |timestamp, temp, summary| Weather {
+            timestamp,
+            temp,
+            ..Weather::test(Some(summary))
+        };
+        let report = report(
+            vec![
+                at(now, 50.0, "Clear"),
+                at(now + Duration::hours(2), 55.0, "Cloudy"),
+                at(now + Duration::hours(12), 48.0, "Rain"),
+            ],
+            Vec::new(),
+        );
+
+        assert_eq!(
+            vec![
+                "Leaving at 8am, it'll be 55 and Cloudy.",
+                "Heading home at 6pm, it'll be 48 and Rain.",
+            ],
+            to_commute(report, 8, 18, NO_SUMMARIZER, &SpeechOptions::default()).await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_commute_leaving_within_the_hour() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 7, 0, 0)
+            .unwrap();
+        let mut report = report_at(now, 1..12);
+        report.hourly[0].summary = "Cloudy".to_string();
+        report.weather.push(Weather {
+            timestamp: now + Duration::hours(11),
+            ..Weather::test(Some("Clear"))
+        });
+
+        assert_eq!(
+            vec![
+                "Leaving at 8am, it'll be 72 and Cloudy.",
+                "Heading home at 6pm, it'll be 72 and Clear.",
+            ],
+            to_commute(report, 8, 18, NO_SUMMARIZER, &SpeechOptions::default()).await?
+        );
+
+        // Already leaving, and the hourly series has moved past 8am.
+        let report = report_at(now + Duration::minutes(75), 1..4);
+        assert_eq!(
+            vec!["Leaving at 8am, it'll be 72 and Rain."],
+            to_commute(report, 8, 18, NO_SUMMARIZER, &SpeechOptions::default()).await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_commute_tomorrow() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 20, 0, 0)
+            .unwrap();
+        let report = report(
+            vec![
+                Weather {
+                    timestamp: now,
+                    ..Weather::test(Some("Clear"))
+                },
+                Weather {
+                    timestamp: now + Duration::hours(12),
+                    ..Weather::test(Some("Clear"))
+                },
+            ],
+            Vec::new(),
+        );
+
+        let commute = to_commute(report, 8, 18, NO_SUMMARIZER, &SpeechOptions::default()).await?;
+        assert_eq!(
+            vec!["Leaving tomorrow at 8am, it'll be 72 and Clear."],
+            commute
+        );
+
+        let report = report_at(now, 0..0);
+        let commute = to_commute(report, 8, 18, NO_SUMMARIZER, &SpeechOptions::default()).await?;
+        assert_eq!(
+            vec!["I don't have a forecast for your commute yet."],
+            commute
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_forecast_for_time() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap();
+        let mut report = report_at(now, 1..48);
+        report.hourly[5].precip_probability = Some(0.6);
+
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let options = SpeechOptions::default();
+
+        assert_eq!(
+            "At 3pm, it will be 72 and Rain with a 60 percent chance of rain.",
+            to_forecast_for_time(report, time(15, 0), &options)?
+        );
+
+        // Times between hours go to the nearer one, and times already passed
+        // mean tomorrow.
+        assert_eq!(
+            "Tomorrow at 8am, it will be 72 and Rain.",
+            to_forecast_for_time(report_at(now, 1..48), time(7, 45), &options)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_forecast_for_time_beyond_hourly_data() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            "I don't have a forecast for 8am tomorrow yet.",
+            to_forecast_for_time(
+                report_at(now, 1..12),
+                NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                &SpeechOptions::default()
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_capitalize() {
+        assert_eq!("Tomorrow", capitalize("tomorrow"));
+        assert_eq!("Tuesday", capitalize("Tuesday"));
+        assert_eq!("", capitalize(""));
+    }
+
+    #[test]
+    fn test_help_keeps_session_open() {
+        let response = &help()["response"];
+
+        assert_eq!(false, response["shouldEndSession"]);
+        assert!(
+            response["outputSpeech"]["ssml"]
+                .as_str()
+                .unwrap()
+                .contains("<s>Would you like the forecast?</s>")
+        );
+        assert_eq!(
+            "<speak><s>Would you like the forecast?</s></speak>",
+            response["reprompt"]["outputSpeech"]["ssml"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forecast_for_today_reads_the_usual_forecast() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap();
+        let report = report_at(now, 1..4);
+        let options = SpeechOptions::default();
+
+        assert_eq!(
+            forecast(report.clone(), NO_SUMMARIZER, &options).await?,
+            forecast_for_date(report, now.date_naive(), NO_SUMMARIZER, &options).await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_forecast_for_past_date_is_declined() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2024, 1, 14).unwrap();
+
+        let response = forecast_for_date(
+            report_at(now, 1..4),
+            yesterday,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(past_date(), response);
+        assert_eq!(true, response["response"]["shouldEndSession"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_goodbye_ends_session() {
+        let response = &goodbye()["response"];
+
+        assert_eq!(true, response["shouldEndSession"]);
+        assert_eq!(
+            "<speak><s>Goodbye.</s></speak>",
+            response["outputSpeech"]["ssml"]
+        );
+        assert!(response.get("reprompt").is_none());
+    }
+
+    #[test]
+    fn test_empty_has_no_speech() {
+        assert_eq!(json!({"version": "1.0", "response": {}}), empty());
+    }
+
+    #[tokio::test]
+    async fn test_forecast_renders_ssml() -> Result<()> {
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Wind & Snow Advisory".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now + Duration::hours(2),
+            end: now + Duration::hours(18),
+            description: "Test alert".to_string(),
+        }];
+
+        let response = forecast(
+            report(vec![Weather::test(Some("sunny"))], alerts),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(true, response["response"]["shouldEndSession"]);
+
+        let speech = &response["response"]["outputSpeech"];
+        assert_eq!("SSML", speech["type"]);
+
+        let ssml = speech["ssml"].as_str().unwrap();
+        assert!(ssml.starts_with("<speak><s>It&apos;s currently 72 and sunny.</s>"));
+        assert!(ssml.contains(r#"<break time="500ms"/><s>There is a wind &amp; snow advisory"#));
+        assert!(ssml.contains(r#"<say-as interpret-as="time">"#));
+        assert!(ssml.ends_with("</s></speak>"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_speech_matches_forecast() -> Result<()> {
+        let weather = vec![Weather::test(Some("sunny")), Weather::test(Some("cloudy"))];
+
+        let speech = speech(
+            report(weather.clone(), Vec::new()),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+        let response = forecast(
+            report(weather, Vec::new()),
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert!(
+            speech
+                .text()
+                .starts_with("It's currently 72 and sunny. At ")
+        );
+        assert_eq!(response["response"]["outputSpeech"]["ssml"], speech.ssml());
+        assert_eq!(response, speech.response());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_with_one_alert() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Small Craft Advisory".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now + Duration::hours(2),
+            end: now + Duration::hours(18),
+            description: "Test alert".to_string(),
+        }];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(forecast[1].contains("There is a"));
+        assert!(forecast[1].contains("small craft advisory"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_narrates_rain() -> Result<()> {
+        let now = Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap();
+        let at = |hours, chance, summary| Weather {
+            timestamp: now + Duration::hours(hours),
+            precip_probability: Some(chance),
+            ..Weather::test(Some(summary))
+        };
+
+        let report = WeatherReport {
+            weather: vec![
+                at(0, 0.0, "Cloudy"),
+                at(3, 0.6, "Rain"),
+                at(9, 0.1, "Cloudy"),
+            ],
+            hourly: (1..15)
+                .map(|h| match h {
+                    3..=6 => at(h, 0.6, "Rain"),
+                    _ => at(h, 0.1, "Cloudy"),
+                })
+                .collect(),
+            daily: Vec::new(),
+            alerts: Vec::new(),
+        };
+
+        assert_eq!(
+            vec![
+                "It's currently 72 and Cloudy.",
+                "Rain starting around noon and ending by 4pm.",
+                "At noon, it will be 72 and Rain.",
+                "And at 6pm it will be 72 and Cloudy.",
+                "Bring an umbrella for the afternoon.",
+            ],
+            to_forecast(report, None, NO_SUMMARIZER, &SpeechOptions::default()).await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_advice_comes_before_alerts() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather {
+            temp: 40.0,
+            ..Weather::test(Some("sunny"))
+        }];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Frost Advisory".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now + Duration::hours(2),
+            end: now + Duration::hours(18),
+            description: "Test alert".to_string(),
+        }];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(3, forecast.len());
+        assert!(forecast[1].starts_with("You'll want a jacket"));
+        assert!(forecast[2].contains("frost advisory"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_with_multiple_alerts() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![
+            WeatherAlert {
+                event: "Winter Storm Warning".to_string(),
+                sender_name: "NWS".to_string(),
+                start: now + Duration::hours(6),
+                end: now + Duration::hours(30),
+                description: "Test alert 1".to_string(),
+            },
+            WeatherAlert {
+                event: "Flood Watch".to_string(),
+                sender_name: "NWS".to_string(),
+                start: now + Duration::hours(8),
+                end: now + Duration::hours(32),
+                description: "Test alert 2".to_string(),
+            },
+            WeatherAlert {
+                event: "High Wind Warning".to_string(),
+                sender_name: "NWS".to_string(),
+                start: now + Duration::hours(10),
+                end: now + Duration::hours(34),
+                description: "Test alert 3".to_string(),
+            },
+        ];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(forecast[1].contains("There is a"));
+        assert!(forecast[1].contains("winter storm warning"));
+        assert!(forecast[1].contains("flood watch"));
+        assert!(forecast[1].contains("And 1 more alert"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_vague_alert_with_phenomenon_uses_will_be() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Special Weather Statement".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now - Duration::hours(1),
+            end: now + Duration::hours(2),
+            description: "Areas of fog continue early this morning, with visibilities \
+                          ranging between one and one-quarter mile."
+                .to_string(),
+        }];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(
+            forecast[1].contains("There will be areas of fog"),
+            "Expected phenomenon-based phrasing, got: {}",
+            forecast[1]
+        );
+        assert!(!forecast[1].contains("special weather statement"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_vague_alert_without_phenomenon_falls_back() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Special Weather Statement".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now - Duration::hours(1),
+            end: now + Duration::hours(2),
+            description: "A generic advisory with no specific phenomenon mentioned.".to_string(),
+        }];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(forecast[1].contains("There is a special weather statement"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_forecast_mixed_alerts() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![
+            WeatherAlert {
+                event: "Special Weather Statement".to_string(),
+                sender_name: "NWS".to_string(),
+                start: now - Duration::hours(1),
+                end: now + Duration::hours(2),
+                description: "Areas of dense fog through late morning.".to_string(),
+            },
+            WeatherAlert {
+                event: "Flood Watch".to_string(),
+                sender_name: "NWS".to_string(),
+                start: now + Duration::hours(3),
+                end: now + Duration::hours(12),
+                description: "Flooding possible in low areas.".to_string(),
+            },
+        ];
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            NO_SUMMARIZER,
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(forecast[1].contains("There will be dense fog"));
+        assert!(forecast[1].contains("And a flood watch"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_speakable_timestamp_midnight() {
+        use chrono::NaiveDate;
+        let dt = Tz::UTC
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2024, 1, 15)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(speakable_timestamp(&dt), "midnight");
+    }
+
+    #[test]
+    fn test_speakable_timestamp_noon() {
+        use chrono::NaiveDate;
+        let dt = Tz::UTC
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2024, 1, 15)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(speakable_timestamp(&dt), "noon");
+    }
+
+    #[test]
+    fn test_speakable_timestamp_normal_hours() {
+        use chrono::NaiveDate;
+
+        let am_time = Tz::UTC
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2024, 1, 15)
+                    .unwrap()
+                    .and_hms_opt(8, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(speakable_timestamp(&am_time), "8am");
+
+        let pm_time = Tz::UTC
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2024, 1, 15)
+                    .unwrap()
+                    .and_hms_opt(18, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(speakable_timestamp(&pm_time), "6pm");
+    }
+
+    #[test]
+    fn test_format_alert_timerange_same_day() {
+        use chrono::NaiveDate;
+
+        // Use fixed noon time so start and end always stay on the same day regardless of when
+        // the test runs. noon + 6h = 6pm, both on the same date.
+        let noon = Tz::UTC
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2099, 6, 15)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        let start = noon;
+        let end = noon + chrono::Duration::hours(6);
+
+        let result = format_alert_timerange(&start, &end);
+        // speakable_timestamp(noon) = "noon", speakable_timestamp(6pm) = "6pm"
+        // Same day → day label should appear only once at the end
+        assert!(
+            result.starts_with("from noon through 6pm "),
+            "Expected 'from noon through 6pm <day>', got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_format_alert_timerange_different_days() {
+        use chrono::Duration;
+
+        // Use tomorrow to ensure start is always in the future
+        let now = Utc::now().with_timezone(&Tz::UTC);
+        let tomorrow = (now + Duration::days(1)).date_naive();
+
+        let start = Tz::UTC
+            .from_local_datetime(&tomorrow.and_hms_opt(8, 0, 0).unwrap())
+            .unwrap();
+
+        let end = start + Duration::days(2) + Duration::hours(4); // 12pm two days later
+
+        let result = format_alert_timerange(&start, &end);
+        assert!(result.contains("from 8am"));
+        assert!(result.contains("through noon"));
+        // Should have two day mentions (different days)
+        assert!(result.contains("8am "));
+        assert!(result.contains(" noon "));
+        // Verify format has both day mentions
+        let parts: Vec<&str> = result.split_whitespace().collect();
+        assert!(parts.len() >= 6); // "from 8am [day] through noon [day]"
+    }
+
+    #[test]
+    fn test_format_alert_timerange_yesterday_to_today() {
+        use chrono::Duration;
+
+        // Create an alert that started yesterday and ends today
+        let now = Utc::now().with_timezone(&Tz::UTC);
+        let today = now.date_naive();
+        let yesterday = today - Duration::days(1);
+
+        let start = Tz::UTC
+            .from_local_datetime(&yesterday.and_hms_opt(23, 0, 0).unwrap()) // 11pm yesterday
+            .unwrap();
+
+        let end = Tz::UTC
+            .from_local_datetime(&today.and_hms_opt(10, 0, 0).unwrap()) // 10am today
+            .unwrap();
+
+        let result = format_alert_timerange(&start, &end);
+        // Should omit "yesterday" and "from", use "until" for past alerts
+        assert!(
+            !result.contains("yesterday"),
+            "Result should not contain 'yesterday': {}",
+            result
+        );
+        assert!(
+            !result.contains("from"),
+            "Result should not contain 'from': {}",
+            result
+        );
+        assert!(
+            result.starts_with("until"),
+            "Result should start with 'until': {}",
+            result
+        );
+        assert!(result.contains("10am"));
+        assert!(result.contains("today"));
+    }
+
+    #[test]
+    fn test_format_alert_timerange_midnight_ending_tonight() {
+        use chrono::Duration;
+
+        // An alert ending at the upcoming midnight (00:00 tomorrow) should read
+        // "midnight tonight", not "midnight tomorrow" — the instant belongs to
+        // the end of today.
+        let now = Utc::now().with_timezone(&Tz::UTC);
+        let tomorrow = now.date_naive() + Duration::days(1);
+
+        let start = now - Duration::hours(2); // started in the past
+        let end = Tz::UTC
+            .from_local_datetime(&tomorrow.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap();
+
+        let result = format_alert_timerange(&start, &end);
+        assert_eq!(
+            result, "until midnight tonight",
+            "Expected 'until midnight tonight', got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_format_alert_timerange_midnight_ending_tomorrow_night() {
+        use chrono::Duration;
+
+        // An alert ending at 00:00 the day after tomorrow caps tomorrow night,
+        // so it should read "midnight tomorrow night".
+        let now = Utc::now().with_timezone(&Tz::UTC);
+        let day_after_tomorrow = now.date_naive() + Duration::days(2);
+
+        let start = now - Duration::hours(2); // started in the past
+        let end = Tz::UTC
+            .from_local_datetime(&day_after_tomorrow.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap();
+
+        let result = format_alert_timerange(&start, &end);
+        assert_eq!(
+            result, "until midnight tomorrow night",
+            "Expected 'until midnight tomorrow night', got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_format_alert_timerange_midnight_start_cross_day() {
+        use chrono::Duration;
+
+        // A future alert starting at the upcoming midnight (00:00 tomorrow) and
+        // ending on a later day exercises the cross-day "from … through …"
+        // branch, routing the midnight `start` through speakable_moment so it
+        // reads "midnight tonight" rather than "midnight tomorrow".
+        let now = Utc::now().with_timezone(&Tz::UTC);
+        let tomorrow = now.date_naive() + Duration::days(1);
+        let day_after_tomorrow = now.date_naive() + Duration::days(2);
+        let expected_end_day = day_after_tomorrow.format("%A").to_string();
+
+        let start = Tz::UTC
+            .from_local_datetime(&tomorrow.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap();
+        let end = Tz::UTC
+            .from_local_datetime(&day_after_tomorrow.and_hms_opt(8, 0, 0).unwrap())
+            .unwrap();
+
+        let result = format_alert_timerange(&start, &end);
+        assert_eq!(
+            result,
+            format!("from midnight tonight through 8am {}", expected_end_day),
+            "Expected midnight start to read 'midnight tonight', got: {}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vague_alert_uses_stub_summarizer() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Special Weather Statement".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now - Duration::hours(1),
+            end: now + Duration::hours(2),
+            // Description that won't match any rule-based phenomenon
+            description: "Unusual conditions in the area today.".to_string(),
+        }];
+
+        let stub = StubSummarizer {
+            phrase: Some("gusty crosswinds".to_string()),
+        };
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            Some(&stub),
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(
+            forecast[1].contains("There will be gusty crosswinds"),
+            "Expected phenomenon from stub summarizer, got: {}",
+            forecast[1]
+        );
+        assert!(!forecast[1].contains("special weather statement"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_vague_alert_falls_back_on_summarizer_error() -> Result<()> {
+        use chrono::Duration;
+
+        let weather = vec![Weather::test(Some("sunny"))];
+        let now = Utc::now().with_timezone(&Tz::UTC);
+
+        let alerts = vec![WeatherAlert {
+            event: "Special Weather Statement".to_string(),
+            sender_name: "NWS".to_string(),
+            start: now - Duration::hours(1),
+            end: now + Duration::hours(2),
+            description: "Unusual conditions in the area today.".to_string(),
+        }];
+
+        let stub = StubSummarizer { phrase: None };
+
+        let forecast = to_forecast(
+            report(weather, alerts),
+            None,
+            Some(&stub),
+            &SpeechOptions::default(),
+        )
+        .await?;
+
+        assert_eq!(2, forecast.len());
+        assert!(
+            forecast[1].contains("There is a special weather statement"),
+            "Expected event-name fallback, got: {}",
+            forecast[1]
+        );
+
+        Ok(())
+    }
+}
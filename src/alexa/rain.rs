@@ -0,0 +1,249 @@
+//! When precipitation starts and stops, from the full hourly series rather
+//! than just the scheduled hours, e.g. "Rain starting around 2pm and ending
+//! by 7pm."
+
+use super::{capitalize, precip_kind, relative_day, speakable_timestamp};
+use crate::weather::Weather;
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+
+/// Summaries that mean rain, for sources that don't give a probability.
+const RAINY_SUMMARIES: [&str; 5] = ["rain", "shower", "drizzle", "thunder", "storm"];
+
+/// Whether precipitation is likely enough to mention: its probability
+/// reaches `threshold`, or the summary says it's raining if there's no
+/// probability.
+pub(super) fn is_wet(weather: &Weather, threshold: f64) -> bool {
+    match weather.precip_probability {
+        Some(probability) => probability > 0.0 && probability >= threshold,
+        None => {
+            let summary = weather.summary.to_lowercase();
+            RAINY_SUMMARIES.iter().any(|rainy| summary.contains(rainy))
+        }
+    }
+}
+
+/// The first stretch of wet hours on a day.
+#[derive(Debug, PartialEq)]
+pub(super) struct Spell {
+    /// `None` if it's already wet when the day's forecast begins.
+    start: Option<DateTime<Tz>>,
+    /// The first dry hour after, or `None` if it's wet for the rest of the
+    /// day.
+    end: Option<DateTime<Tz>>,
+    /// When it's wet again later in the day, if it is.
+    again: Option<DateTime<Tz>>,
+    /// "rain" or "snow".
+    kind: &'static str,
+}
+
+impl Spell {
+    /// Scans `current` and every hour of `hourly` after it that falls on
+    /// `date`. Returns `None` if it's dry throughout, or wet throughout, as
+    /// there's no change to narrate.
+    pub(super) fn find(
+        current: &Weather,
+        hourly: &[Weather],
+        date: NaiveDate,
+        threshold: f64,
+    ) -> Option<Self> {
+        let hours: Vec<&Weather> = [current]
+            .into_iter()
+            .chain(hourly.iter().filter(|w| w.timestamp > current.timestamp))
+            .filter(|w| w.timestamp.date_naive() == date)
+            .collect();
+
+        let first_wet = hours.iter().position(|w| is_wet(w, threshold))?;
+        let end = hours[first_wet..]
+            .iter()
+            .position(|w| !is_wet(w, threshold))
+            .map(|dry| first_wet + dry);
+
+        if first_wet == 0 && end.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            start: (first_wet > 0).then(|| hours[first_wet].timestamp),
+            end: end.map(|end| hours[end].timestamp),
+            again: end
+                .and_then(|end| hours[end..].iter().find(|w| is_wet(w, threshold)))
+                .map(|w| w.timestamp),
+            kind: precip_kind(&hours[first_wet].summary),
+        })
+    }
+
+    /// Whether `timestamp` falls within the spell, so its hour needn't
+    /// mention the chance of precipitation again.
+    pub(super) fn covers(&self, timestamp: &DateTime<Tz>) -> bool {
+        self.start.is_none_or(|start| start <= *timestamp)
+            && self.end.is_none_or(|end| *timestamp < end)
+    }
+
+    /// E.g. "Rain starting around 2pm and ending by 7pm.", led by the day if
+    /// it isn't the same as `now`'s.
+    pub(super) fn speakable(&self, now: &DateTime<Tz>) -> String {
+        let mut times = Vec::with_capacity(2);
+        if let Some(start) = &self.start {
+            times.push(format!("starting around {}", speakable_timestamp(start)));
+        }
+        if let Some(end) = &self.end {
+            times.push(format!("ending by {}", speakable_timestamp(end)));
+        }
+
+        let mut sentence = match self.start.or(self.end) {
+            Some(day) if day.date_naive() != now.date_naive() => {
+                format!("{}, {}", capitalize(&relative_day(&day, now)), self.kind)
+            }
+            _ => capitalize(self.kind),
+        };
+        sentence.push(' ');
+        sentence.push_str(&times.join(" and "));
+        if let Some(again) = &self.again {
+            sentence.push_str(&format!(
+                ", then again around {}",
+                speakable_timestamp(again)
+            ));
+        }
+        sentence.push('.');
+
+        sentence
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Tz> {
+        Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, 9, 0, 0)
+            .unwrap()
+    }
+
+    /// Consecutive hours from `start` with the given chances of
+    /// precipitation.
+    fn hourly(start: DateTime<Tz>, chances: &[f64]) -> Vec<Weather> {
+        chances
+            .iter()
+            .enumerate()
+            .map(|(hour, &chance)| Weather {
+                timestamp: start + Duration::hours(hour as i64),
+                precip_probability: Some(chance),
+                ..Weather::test(Some("Cloudy"))
+            })
+            .collect()
+    }
+
+    fn current(chance: f64) -> Weather {
+        Weather {
+            timestamp: now(),
+            precip_probability: Some(chance),
+            ..Weather::test(Some("Cloudy"))
+        }
+    }
+
+    fn find(current: &Weather, hourly: &[Weather]) -> Option<Spell> {
+        Spell::find(current, hourly, now().date_naive(), 0.3)
+    }
+
+    #[test]
+    fn test_is_wet() {
+        let weather = Weather::test(Some("Light Drizzle"));
+        assert!(is_wet(&weather, 0.3));
+
+        let weather = Weather {
+            precip_probability: Some(0.2),
+            ..weather
+        };
+        assert!(!is_wet(&weather, 0.3));
+        assert!(is_wet(&weather, 0.2));
+        assert!(!is_wet(&Weather::test(Some("Clear")), 0.0));
+    }
+
+    #[test]
+    fn test_starting_and_ending() {
+        // 10am through 9pm.
+        let hourly = hourly(
+            now() + Duration::hours(1),
+            &[0.0, 0.1, 0.2, 0.1, 0.6, 0.8, 0.7, 0.4, 0.9, 0.2, 0.1, 0.0],
+        );
+
+        let spell = find(&current(0.0), &hourly).unwrap();
+        assert_eq!(
+            "Rain starting around 2pm and ending by 7pm.",
+            spell.speakable(&now())
+        );
+
+        assert!(!spell.covers(&hourly[3].timestamp));
+        assert!(spell.covers(&hourly[4].timestamp));
+        assert!(spell.covers(&hourly[8].timestamp));
+        assert!(!spell.covers(&hourly[9].timestamp));
+    }
+
+    #[test]
+    fn test_ending() {
+        let hourly = hourly(now() + Duration::hours(1), &[0.7, 0.5, 0.1, 0.0]);
+
+        let spell = find(&current(0.9), &hourly).unwrap();
+        assert_eq!("Rain ending by noon.", spell.speakable(&now()));
+        assert!(spell.covers(&now()));
+    }
+
+    #[test]
+    fn test_starting_then_again() {
+        let hourly = hourly(
+            now() + Duration::hours(1),
+            &[0.0, 0.6, 0.0, 0.0, 0.0, 0.6, 0.6],
+        );
+
+        let spell = find(&current(0.0), &hourly).unwrap();
+        assert_eq!(
+            "Rain starting around 11am and ending by noon, then again around 3pm.",
+            spell.speakable(&now())
+        );
+        assert!(!spell.covers(&hourly[5].timestamp));
+    }
+
+    #[test]
+    fn test_snow_starting() {
+        let hourly = vec![Weather {
+            summary: "Snow".to_string(),
+            ..hourly(now() + Duration::hours(1), &[0.6]).remove(0)
+        }];
+
+        let spell = find(&current(0.0), &hourly).unwrap();
+        assert_eq!("Snow starting around 10am.", spell.speakable(&now()));
+    }
+
+    #[test]
+    fn test_nothing_to_narrate() {
+        let dry = hourly(now() + Duration::hours(1), &[0.0, 0.1, 0.2]);
+        assert_eq!(None, find(&current(0.0), &dry));
+
+        let wet = hourly(now() + Duration::hours(1), &[0.6, 0.7, 0.8]);
+        assert_eq!(None, find(&current(0.9), &wet));
+
+        assert_eq!(None, find(&current(0.9), &[]));
+    }
+
+    #[test]
+    fn test_stops_at_end_of_day() {
+        // 10pm tonight through 1am tomorrow.
+        let hourly = hourly(now() + Duration::hours(13), &[0.0, 0.6, 0.0, 0.0]);
+
+        let spell = find(&current(0.0), &hourly).unwrap();
+        assert_eq!("Rain starting around 11pm.", spell.speakable(&now()));
+    }
+
+    #[test]
+    fn test_tomorrow() {
+        let tomorrow = (now() + Duration::days(1)).date_naive();
+        // 7am through 10am tomorrow.
+        let hourly = hourly(now() + Duration::hours(22), &[0.8, 0.6, 0.0, 0.0]);
+
+        let spell = Spell::find(&current(0.0), &hourly, tomorrow, 0.3).unwrap();
+        assert_eq!("Tomorrow, rain ending by 9am.", spell.speakable(&now()));
+    }
+}
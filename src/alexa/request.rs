@@ -0,0 +1,348 @@
+//! The Alexa request envelope, as delivered to the skill's endpoint.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+pub struct RequestEnvelope {
+    pub version: String,
+    pub session: Option<Session>,
+    pub context: Option<Context>,
+    pub request: Request,
+}
+
+impl RequestEnvelope {
+    /// The skill the request is for, which is in the context of every request
+    /// and also in the session when there is one.
+    pub fn application_id(&self) -> Option<&str> {
+        self.context
+            .as_ref()
+            .map(|context| &context.system.application)
+            .or(self.session.as_ref().map(|session| &session.application))
+            .map(|application| application.application_id.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Session {
+    #[serde(alias = "sessionId")]
+    pub session_id: String,
+    pub application: Application,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Context {
+    #[serde(alias = "System")]
+    pub system: System,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct System {
+    pub application: Application,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Application {
+    #[serde(alias = "applicationId")]
+    pub application_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum Request {
+    LaunchRequest(RequestInfo),
+    IntentRequest {
+        #[serde(flatten)]
+        info: RequestInfo,
+        intent: Intent,
+    },
+    SessionEndedRequest {
+        #[serde(flatten)]
+        info: RequestInfo,
+        reason: Option<String>,
+    },
+    /// Request types the skill doesn't handle, e.g. `CanFulfillIntentRequest`.
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Fields common to every request type.
+#[derive(Debug, Deserialize)]
+pub struct RequestInfo {
+    #[serde(alias = "requestId")]
+    pub request_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Intent {
+    pub name: String,
+    #[serde(default)]
+    pub slots: HashMap<String, Slot>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Slot {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+pub const FORECAST_FOR_DATE_INTENT: &str = "GetForecastForDateIntent";
+/// `AMAZON.DATE` slot on [`FORECAST_FOR_DATE_INTENT`].
+pub const DATE_SLOT: &str = "date";
+
+pub const FORECAST_FOR_TIME_INTENT: &str = "GetForecastForTimeIntent";
+/// `AMAZON.TIME` slot on [`FORECAST_FOR_TIME_INTENT`].
+pub const TIME_SLOT: &str = "time";
+
+pub const HELP_INTENT: &str = "AMAZON.HelpIntent";
+pub const FALLBACK_INTENT: &str = "AMAZON.FallbackIntent";
+pub const STOP_INTENT: &str = "AMAZON.StopIntent";
+pub const CANCEL_INTENT: &str = "AMAZON.CancelIntent";
+
+/// What the skill should do in response to a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Read the forecast and end the session.
+    Forecast,
+    /// Read the forecast for a particular day and end the session.
+    ForecastForDate(NaiveDate),
+    /// Read the forecast for the hour nearest a time of day and end the
+    /// session.
+    ForecastForTime(NaiveTime),
+    /// Explain how to use the skill and keep the session open.
+    Help,
+    /// Say goodbye and end the session.
+    Stop,
+    /// Respond without speech, e.g. once the session has ended. No weather is
+    /// fetched.
+    Ignore,
+}
+
+impl Request {
+    pub fn route(&self) -> Route {
+        match self {
+            Request::LaunchRequest(_) => Route::Forecast,
+            Request::IntentRequest { intent, .. } => match intent.name.as_str() {
+                FORECAST_FOR_DATE_INTENT => intent
+                    .slot_value(DATE_SLOT)
+                    .and_then(parse_amazon_date)
+                    .map_or(Route::Forecast, Route::ForecastForDate),
+                FORECAST_FOR_TIME_INTENT => intent
+                    .slot_value(TIME_SLOT)
+                    .and_then(parse_amazon_time)
+                    .map_or(Route::Forecast, Route::ForecastForTime),
+                HELP_INTENT | FALLBACK_INTENT => Route::Help,
+                STOP_INTENT | CANCEL_INTENT => Route::Stop,
+                _ => Route::Forecast,
+            },
+            Request::SessionEndedRequest { .. } | Request::Unsupported => Route::Ignore,
+        }
+    }
+
+    /// Fields common to every request type, or `None` for unsupported types.
+    pub fn info(&self) -> Option<&RequestInfo> {
+        match self {
+            Request::LaunchRequest(info)
+            | Request::IntentRequest { info, .. }
+            | Request::SessionEndedRequest { info, .. } => Some(info),
+            Request::Unsupported => None,
+        }
+    }
+}
+
+impl Intent {
+    /// The value of the named slot, if the user filled it.
+    pub fn slot_value(&self, name: &str) -> Option<&str> {
+        self.slots.get(name)?.value.as_deref()
+    }
+}
+
+/// Resolves an `AMAZON.DATE` value to a single day. Handles specific dates
+/// ("2024-01-20") and weekends ("2024-W03-WE", read as the Saturday); other
+/// values, like whole weeks or months, return `None`.
+pub fn parse_amazon_date(value: &str) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let (year, week) = value.strip_suffix("-WE")?.split_once("-W")?;
+    NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Sat)
+}
+
+/// Resolves an `AMAZON.TIME` value to a time of day. Handles clock times
+/// ("15:00") and the morning, afternoon, evening and night periods ("MO",
+/// "AF", "EV", "NI").
+pub fn parse_amazon_time(value: &str) -> Option<NaiveTime> {
+    let hour = match value {
+        "MO" => 9,
+        "AF" => 15,
+        "EV" => 18,
+        "NI" => 21,
+        _ => return NaiveTime::parse_from_str(value, "%H:%M").ok(),
+    };
+
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Result;
+
+    const LAUNCH_REQUEST: &str = r#"{"version":"1.0","session":{"new":true,"sessionId":"amzn1.echo-api.session.1234","application":{"applicationId":"amzn1.ask.skill.5678"},"user":{"userId":"amzn1.ask.account.ABCD"}},"context":{"System":{"application":{"applicationId":"amzn1.ask.skill.5678"},"user":{"userId":"amzn1.ask.account.ABCD"},"device":{"deviceId":"amzn1.ask.device.EFGH","supportedInterfaces":{}},"apiEndpoint":"https://api.amazonalexa.com"}},"request":{"type":"LaunchRequest","requestId":"amzn1.echo-api.request.1111","timestamp":"2024-01-15T12:00:00Z","locale":"en-US","shouldLinkResultBeReturned":false}}"#;
+
+    const HELP_REQUEST: &str = r#"{"version":"1.0","session":{"new":false,"sessionId":"amzn1.echo-api.session.1234","application":{"applicationId":"amzn1.ask.skill.5678"},"user":{"userId":"amzn1.ask.account.ABCD"}},"request":{"type":"IntentRequest","requestId":"amzn1.echo-api.request.2222","timestamp":"2024-01-15T12:00:05Z","locale":"en-US","intent":{"name":"AMAZON.HelpIntent","confirmationStatus":"NONE"}}}"#;
+
+    const FORECAST_REQUEST: &str = r#"{"version":"1.0","session":{"new":true,"sessionId":"amzn1.echo-api.session.1234","application":{"applicationId":"amzn1.ask.skill.5678"},"user":{"userId":"amzn1.ask.account.ABCD"}},"request":{"type":"IntentRequest","requestId":"amzn1.echo-api.request.3333","timestamp":"2024-01-15T12:00:10Z","locale":"en-US","intent":{"name":"GetForecastIntent","confirmationStatus":"NONE","slots":{"date":{"name":"date","value":"2024-01-16","confirmationStatus":"NONE"}}}}}"#;
+
+    const SESSION_ENDED_REQUEST: &str = r#"{"version":"1.0","session":{"new":false,"sessionId":"amzn1.echo-api.session.1234","application":{"applicationId":"amzn1.ask.skill.5678"},"user":{"userId":"amzn1.ask.account.ABCD"}},"request":{"type":"SessionEndedRequest","requestId":"amzn1.echo-api.request.4444","timestamp":"2024-01-15T12:00:15Z","locale":"en-US","reason":"USER_INITIATED"}}"#;
+
+    fn parse(json: &str) -> Result<RequestEnvelope> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    #[test]
+    fn test_parse_launch_request() -> Result<()> {
+        let envelope = parse(LAUNCH_REQUEST)?;
+
+        let session = envelope.session.as_ref().unwrap();
+        assert_eq!("amzn1.echo-api.session.1234", session.session_id);
+        assert_eq!("amzn1.ask.skill.5678", session.application.application_id);
+        assert_eq!(Some("amzn1.ask.skill.5678"), envelope.application_id());
+
+        let info = envelope.request.info().unwrap();
+        assert_eq!("amzn1.echo-api.request.1111", info.request_id);
+        assert_eq!("2024-01-15T12:00:00+00:00", info.timestamp.to_rfc3339());
+        assert_eq!(Some("en-US"), info.locale.as_deref());
+
+        assert_eq!(Route::Forecast, envelope.request.route());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_intent_request() -> Result<()> {
+        let envelope = parse(FORECAST_REQUEST)?;
+
+        let Request::IntentRequest { info, intent } = &envelope.request else {
+            panic!("Expected an IntentRequest: {:?}", envelope.request);
+        };
+        assert_eq!("amzn1.echo-api.request.3333", info.request_id);
+        assert_eq!("GetForecastIntent", intent.name);
+        assert_eq!(Some("2024-01-16"), intent.slots["date"].value.as_deref());
+
+        assert_eq!(Route::Forecast, envelope.request.route());
+        Ok(())
+    }
+
+    #[test]
+    fn test_route_forecast_for_date() -> Result<()> {
+        let envelope =
+            parse(&FORECAST_REQUEST.replace("GetForecastIntent", FORECAST_FOR_DATE_INTENT))?;
+        assert_eq!(
+            Route::ForecastForDate(NaiveDate::from_ymd_opt(2024, 1, 16).unwrap()),
+            envelope.request.route()
+        );
+
+        // A whole week can't be read as one day, so fall back to today.
+        let envelope = parse(
+            &FORECAST_REQUEST
+                .replace("GetForecastIntent", FORECAST_FOR_DATE_INTENT)
+                .replace("2024-01-16", "2024-W03"),
+        )?;
+        assert_eq!(Route::Forecast, envelope.request.route());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_amazon_date() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d);
+
+        assert_eq!(date(1, 20), parse_amazon_date("2024-01-20"));
+        assert_eq!(date(1, 20), parse_amazon_date("2024-W03-WE"));
+        assert_eq!(date(3, 2), parse_amazon_date("2024-W09-WE"));
+        assert_eq!(None, parse_amazon_date("2024-W03"));
+        assert_eq!(None, parse_amazon_date("2024-01"));
+        assert_eq!(None, parse_amazon_date("PRESENT_REF"));
+        assert_eq!(None, parse_amazon_date("2024-W99-WE"));
+    }
+
+    #[test]
+    fn test_route_forecast_for_time() -> Result<()> {
+        let envelope = parse(
+            &FORECAST_REQUEST
+                .replace("GetForecastIntent", FORECAST_FOR_TIME_INTENT)
+                .replace(r#""date""#, r#""time""#)
+                .replace("2024-01-16", "15:00"),
+        )?;
+        assert_eq!(
+            Route::ForecastForTime(NaiveTime::from_hms_opt(15, 0, 0).unwrap()),
+            envelope.request.route()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_amazon_time() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+
+        assert_eq!(time(15, 0), parse_amazon_time("15:00"));
+        assert_eq!(time(7, 30), parse_amazon_time("07:30"));
+        assert_eq!(time(9, 0), parse_amazon_time("MO"));
+        assert_eq!(time(21, 0), parse_amazon_time("NI"));
+        assert_eq!(None, parse_amazon_time("25:00"));
+        assert_eq!(None, parse_amazon_time("noon"));
+    }
+
+    #[test]
+    fn test_parse_help_request() -> Result<()> {
+        let envelope = parse(HELP_REQUEST)?;
+
+        let Request::IntentRequest { intent, .. } = &envelope.request else {
+            panic!("Expected an IntentRequest: {:?}", envelope.request);
+        };
+        assert!(intent.slots.is_empty());
+
+        assert_eq!(Route::Help, envelope.request.route());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_session_ended_request() -> Result<()> {
+        let envelope = parse(SESSION_ENDED_REQUEST)?;
+
+        let Request::SessionEndedRequest { reason, .. } = &envelope.request else {
+            panic!("Expected a SessionEndedRequest: {:?}", envelope.request);
+        };
+        assert_eq!(Some("USER_INITIATED"), reason.as_deref());
+
+        assert_eq!(Route::Ignore, envelope.request.route());
+        Ok(())
+    }
+
+    #[test]
+    fn test_route_stop_and_cancel() -> Result<()> {
+        for name in [STOP_INTENT, CANCEL_INTENT] {
+            let envelope = parse(&HELP_REQUEST.replace(HELP_INTENT, name))?;
+            assert_eq!(Route::Stop, envelope.request.route());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unsupported_request() -> Result<()> {
+        let envelope = parse(
+            r#"{"version":"1.0","request":{"type":"CanFulfillIntentRequest","requestId":"amzn1.echo-api.request.5555","timestamp":"2024-01-15T12:00:20Z"}}"#,
+        )?;
+
+        assert!(envelope.session.is_none());
+        assert!(envelope.request.info().is_none());
+        assert_eq!(Route::Ignore, envelope.request.route());
+        Ok(())
+    }
+}
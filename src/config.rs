@@ -0,0 +1,432 @@
+//! Named locations from a TOML config file, e.g.
+//!
+//! ```toml
+//! [locations.home]
+//! latitude = 40.6943
+//! longitude = -73.9249
+//!
+//! [locations.cabin]
+//! latitude = 44.2706
+//! longitude = -71.3033
+//! provider = "nws,openmeteo"
+//! hours = [7, 12, 17]
+//! weekend_hours = [9, 13, 17]
+//!
+//! [commute]
+//! home = "home"
+//! work = "office"
+//! depart = 8
+//! return = 18
+//! ```
+
+use crate::APP_NAME;
+use crate::weather::{HourSchedule, Itinerary, Location, WeatherProvider, validate_coordinates};
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    locations: BTreeMap<String, RawLocation>,
+    commute: Option<RawCommute>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCommute {
+    home: String,
+    work: String,
+    depart: u32,
+    #[serde(rename = "return")]
+    return_hour: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLocation {
+    latitude: f64,
+    longitude: f64,
+    provider: Option<String>,
+    hours: Option<Vec<u32>>,
+    weekend_hours: Option<Vec<u32>>,
+}
+
+/// A location from the config file, with any settings that override the
+/// command line's.
+#[derive(Debug, Clone)]
+pub struct NamedLocation {
+    pub name: String,
+    pub location: Location,
+    pub provider: Option<WeatherProvider>,
+    pub hours: Option<Vec<u32>>,
+    pub weekend_hours: Option<Vec<u32>>,
+}
+
+impl NamedLocation {
+    /// This location's hours, falling back to `default` for any it doesn't
+    /// set.
+    pub fn schedule(&self, default: &HourSchedule) -> HourSchedule {
+        HourSchedule {
+            weekday: self.hours.clone().or_else(|| default.weekday.clone()),
+            weekend: self
+                .weekend_hours
+                .clone()
+                .or_else(|| default.weekend.clone()),
+            add_weekend_hour: default.add_weekend_hour,
+        }
+    }
+
+    fn try_from_raw(name: &str, raw: RawLocation) -> Result<Self> {
+        validate_coordinates(raw.latitude, raw.longitude)?;
+
+        let provider = raw.provider.map(|provider| provider.parse()).transpose()?;
+
+        for hours in [&raw.hours, &raw.weekend_hours].into_iter().flatten() {
+            if let Some(hour) = hours.iter().find(|&&hour| hour > 23) {
+                return Err(anyhow!("Hours must be between 0 and 23, got: {hour}"));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            location: Location::new(raw.latitude, raw.longitude),
+            provider,
+            hours: raw.hours,
+            weekend_hours: raw.weekend_hours,
+        })
+    }
+}
+
+/// A daily commute between two named locations.
+#[derive(Debug, Clone)]
+pub struct Commute {
+    pub home: NamedLocation,
+    pub work: NamedLocation,
+    /// Hour of the day, 0–23, to leave home.
+    pub depart: u32,
+    /// Hour of the day, 0–23, to head home from work.
+    pub return_hour: u32,
+}
+
+impl Commute {
+    /// Home until leaving, then work.
+    pub fn itinerary(&self) -> Itinerary {
+        Itinerary::new(self.home.location).then(self.depart + 1, self.work.location)
+    }
+
+    /// Just the departure and return hours, every day of the week.
+    pub fn schedule(&self) -> HourSchedule {
+        HourSchedule {
+            weekday: Some(vec![self.depart, self.return_hour]),
+            weekend: None,
+            add_weekend_hour: false,
+        }
+    }
+
+    fn try_from_raw(raw: RawCommute, locations: &[NamedLocation]) -> Result<Self> {
+        let find = |name: &str| {
+            locations
+                .iter()
+                .find(|location| location.name == name)
+                .cloned()
+                .ok_or_else(|| anyhow!("Unknown location: {name:?}"))
+        };
+
+        if raw.return_hour > 23 {
+            return Err(anyhow!(
+                "Hours must be between 0 and 23, got: {}",
+                raw.return_hour
+            ));
+        }
+        if raw.depart >= raw.return_hour {
+            return Err(anyhow!(
+                "Departure ({}) must be before return ({})",
+                raw.depart,
+                raw.return_hour
+            ));
+        }
+
+        Ok(Self {
+            home: find(&raw.home)?,
+            work: find(&raw.work)?,
+            depart: raw.depart,
+            return_hour: raw.return_hour,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Sorted by name.
+    pub locations: Vec<NamedLocation>,
+    pub commute: Option<Commute>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/jakesky/config.toml`, or `~/.config/jakesky/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join(APP_NAME).join("config.toml"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let toml = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+        Self::parse(&toml).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Parses and validates every location, naming the offending one in any
+    /// error.
+    pub fn parse(toml: &str) -> Result<Self> {
+        let raw: RawConfig = toml::from_str(toml)?;
+
+        let locations = raw
+            .locations
+            .into_iter()
+            .map(|(name, location)| {
+                NamedLocation::try_from_raw(&name, location)
+                    .with_context(|| format!("Invalid location {name:?}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let commute = raw
+            .commute
+            .map(|commute| Commute::try_from_raw(commute, &locations))
+            .transpose()
+            .context("Invalid commute")?;
+
+        Ok(Self { locations, commute })
+    }
+
+    pub fn location(&self, name: &str) -> Result<&NamedLocation> {
+        self.locations
+            .iter()
+            .find(|location| location.name == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown location: {name:?} (expected one of: {})",
+                    self.locations
+                        .iter()
+                        .map(|location| location.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let config = Config::parse(
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+
+            [locations.cabin]
+            latitude = 44.2706
+            longitude = -71.3033
+            provider = "nws,openmeteo"
+            hours = [7, 12, 17]
+            "#,
+        )?;
+
+        let names: Vec<_> = config.locations.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["cabin", "home"], names);
+
+        let home = config.location("home")?;
+        assert_eq!(Location::new(40.6943, -73.9249), home.location);
+        assert!(home.provider.is_none());
+
+        let cabin = config.location("cabin")?;
+        assert_eq!(
+            vec!["nws", "openmeteo"],
+            cabin.provider.as_ref().unwrap().ids()
+        );
+
+        let default = HourSchedule {
+            weekday: Some(vec![8]),
+            weekend: Some(vec![10]),
+            add_weekend_hour: true,
+        };
+        assert_eq!(
+            HourSchedule {
+                weekday: Some(vec![7, 12, 17]),
+                weekend: Some(vec![10]),
+                add_weekend_hour: true,
+            },
+            cabin.schedule(&default)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_empty() -> Result<()> {
+        assert!(Config::parse("")?.locations.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_names_invalid_coordinates() {
+        let e = Config::parse(
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+
+            [locations.office]
+            latitude = 140.7
+            longitude = -74.0
+            "#,
+        )
+        .unwrap_err();
+
+        let message = format!("{e:#}");
+        assert!(
+            message.contains(r#"Invalid location "office""#),
+            "{message}"
+        );
+        assert!(message.contains("got: 140.7"), "{message}");
+    }
+
+    #[test]
+    fn test_parse_names_invalid_hours() {
+        let e = Config::parse(
+            r#"
+            [locations.cabin]
+            latitude = 44.2706
+            longitude = -71.3033
+            weekend_hours = [9, 24]
+            "#,
+        )
+        .unwrap_err();
+
+        let message = format!("{e:#}");
+        assert!(message.contains(r#"Invalid location "cabin""#), "{message}");
+        assert!(message.contains("got: 24"), "{message}");
+    }
+
+    #[test]
+    fn test_parse_names_invalid_provider() {
+        let e = Config::parse(
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+            provider = "darksky"
+            "#,
+        )
+        .unwrap_err();
+
+        let message = format!("{e:#}");
+        assert!(message.contains(r#"Invalid location "home""#), "{message}");
+        assert!(
+            message.contains("Unknown weather provider: darksky"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        assert!(
+            Config::parse(
+                r#"
+                [locations.home]
+                latitude = 40.6943
+                longitude = -73.9249
+                altitude = 10
+                "#,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_commute() -> Result<()> {
+        let config = Config::parse(
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+
+            [locations.office]
+            latitude = 40.7527
+            longitude = -73.9772
+
+            [commute]
+            home = "home"
+            work = "office"
+            depart = 8
+            return = 18
+            "#,
+        )?;
+
+        let commute = config.commute.unwrap();
+        assert_eq!("home", commute.home.name);
+        assert_eq!("office", commute.work.name);
+        assert_eq!(Some(vec![8, 18]), commute.schedule().weekday);
+
+        let itinerary = commute.itinerary();
+        assert_eq!(commute.home.location, itinerary.location_at(8));
+        assert_eq!(commute.work.location, itinerary.location_at(18));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_commute_names_invalid_entry() {
+        let parse = |commute: &str| {
+            let toml = format!(
+                "[locations.home]\nlatitude = 40.6943\nlongitude = -73.9249\n\n[commute]\n{commute}"
+            );
+            format!("{:#}", Config::parse(&toml).unwrap_err())
+        };
+
+        let message = parse("home = \"home\"\nwork = \"office\"\ndepart = 8\nreturn = 18");
+        assert!(
+            message.contains(r#"Invalid commute: Unknown location: "office""#),
+            "{message}"
+        );
+
+        let message = parse("home = \"home\"\nwork = \"home\"\ndepart = 18\nreturn = 8");
+        assert!(
+            message.contains("Departure (18) must be before return (8)"),
+            "{message}"
+        );
+
+        let message = parse("home = \"home\"\nwork = \"home\"\ndepart = 8\nreturn = 24");
+        assert!(message.contains("got: 24"), "{message}");
+    }
+
+    #[test]
+    fn test_unknown_location() -> Result<()> {
+        let config = Config::parse(
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+            "#,
+        )?;
+
+        let e = config.location("work").unwrap_err();
+        assert_eq!(
+            r#"Unknown location: "work" (expected one of: home)"#,
+            e.to_string()
+        );
+
+        Ok(())
+    }
+}
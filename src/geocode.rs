@@ -0,0 +1,202 @@
+//! Offline lookup of coordinates for US cities and ZIP codes, from tables
+//! bundled into the binary so no geocoding service is needed. The tables are
+//! a small sample of major cities and ZIP codes; `scripts/update-gazetteer.sh`
+//! replaces them with the full GeoNames (CC BY 4.0) data.
+
+use crate::weather::Location;
+use anyhow::{Context, Result, anyhow};
+use std::fmt;
+use std::sync::LazyLock;
+
+/// `name,state,latitude,longitude` for a sample of US cities.
+const PLACES_CSV: &str = include_str!("../data/places.csv");
+
+/// `zip,name,state,latitude,longitude`, with the approximate centroid of a
+/// sample of ZIP codes.
+const ZIPS_CSV: &str = include_str!("../data/zips.csv");
+
+static PLACES: LazyLock<Vec<Place>> =
+    LazyLock::new(|| parse_rows(PLACES_CSV, 4).map(|row| place(&row)).collect());
+
+static ZIPS: LazyLock<Vec<(String, Place)>> = LazyLock::new(|| {
+    parse_rows(ZIPS_CSV, 5)
+        .map(|row| (row[0].to_string(), place(&row[1..])))
+        .collect()
+});
+
+/// A named place and its coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub name: String,
+    /// Two-letter postal abbreviation.
+    pub state: String,
+    pub location: Location,
+}
+
+impl fmt::Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.name, self.state)
+    }
+}
+
+/// Finds a city by name, e.g. "Brooklyn, NY", or just "Brooklyn" if no other
+/// city shares the name. Case and periods are ignored, so "st louis, mo"
+/// matches "St. Louis, MO".
+pub fn find_place(query: &str) -> Result<Place> {
+    let (name, state) = match query.rsplit_once(',') {
+        Some((name, state)) => (name, Some(state.trim())),
+        None => (query, None),
+    };
+    let name = normalize(name);
+
+    let matches: Vec<&Place> = PLACES
+        .iter()
+        .filter(|place| normalize(&place.name) == name)
+        .filter(|place| state.is_none_or(|state| place.state.eq_ignore_ascii_case(state)))
+        .collect();
+
+    match matches.as_slice() {
+        [place] => Ok((*place).clone()),
+        [] => Err(anyhow!(
+            "Unknown place: {query:?} (expected a US city, e.g. \"Brooklyn, NY\")"
+        )),
+        _ => Err(anyhow!(
+            "Ambiguous place: {query:?} (could be {})",
+            matches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )),
+    }
+}
+
+/// Finds the centroid of a five-digit US ZIP code.
+pub fn find_zip(zip: &str) -> Result<Place> {
+    let zip = zip.trim();
+    if zip.len() != 5 || !zip.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(anyhow!("ZIP codes must be five digits, got: {zip:?}"));
+    }
+
+    ZIPS.iter()
+        .find(|(z, _)| z == zip)
+        .map(|(_, place)| place.clone())
+        .with_context(|| format!("Unknown ZIP code: {zip}"))
+}
+
+/// Rows after the header, split into `columns` fields. The tables are
+/// bundled and covered by tests, so malformed rows are a bug.
+fn parse_rows(csv: &str, columns: usize) -> impl Iterator<Item = Vec<&str>> {
+    csv.lines()
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .map(move |line| {
+            let row: Vec<&str> = line.split(',').collect();
+            assert_eq!(columns, row.len(), "Malformed gazetteer row: {line:?}");
+            row
+        })
+}
+
+/// A place from `name,state,latitude,longitude` fields.
+fn place(fields: &[&str]) -> Place {
+    let coordinate = |s: &str| {
+        s.parse::<f64>()
+            .unwrap_or_else(|e| panic!("Malformed gazetteer coordinate {s:?}: {e}"))
+    };
+
+    Place {
+        name: fields[0].to_string(),
+        state: fields[1].to_string(),
+        location: Location::new(coordinate(fields[2]), coordinate(fields[3])),
+    }
+}
+
+/// Lowercases and drops periods and repeated whitespace.
+fn normalize(name: &str) -> String {
+    name.replace('.', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::weather::validate_coordinates;
+
+    /// Within about 5km, so the tests hold as the tables are regenerated.
+    fn assert_near(expected: Location, actual: Location) {
+        assert!(
+            (expected.latitude - actual.latitude).abs() < 0.05
+                && (expected.longitude - actual.longitude).abs() < 0.05,
+            "{actual:?} is not near {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_tables_are_valid() {
+        assert!(!PLACES.is_empty());
+        assert!(!ZIPS.is_empty());
+
+        for place in PLACES.iter().chain(ZIPS.iter().map(|(_, place)| place)) {
+            validate_coordinates(place.location.latitude, place.location.longitude)
+                .unwrap_or_else(|e| panic!("{place}: {e}"));
+            assert_eq!(2, place.state.len(), "{place}");
+        }
+    }
+
+    #[test]
+    fn test_find_place() -> Result<()> {
+        let brooklyn = find_place("Brooklyn, NY")?;
+        assert_eq!("Brooklyn, NY", brooklyn.to_string());
+        assert_near(Location::new(40.6782, -73.9442), brooklyn.location);
+
+        assert_eq!(brooklyn, find_place("  brooklyn ,ny ")?);
+        assert_eq!("Albuquerque, NM", find_place("Albuquerque")?.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_place_ignores_periods() -> Result<()> {
+        assert_eq!("St. Louis, MO", find_place("st louis, mo")?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_place_ambiguous() -> Result<()> {
+        let e = find_place("Portland").unwrap_err().to_string();
+        assert!(e.starts_with(r#"Ambiguous place: "Portland""#), "{e}");
+        assert!(e.contains("Portland, ME"), "{e}");
+        assert!(e.contains("Portland, OR"), "{e}");
+
+        assert_eq!("OR", find_place("Portland, OR")?.state);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_place_unknown() {
+        assert!(find_place("Brooklyn, CA").is_err());
+        assert!(find_place("Gotham").is_err());
+    }
+
+    #[test]
+    fn test_find_zip() -> Result<()> {
+        let place = find_zip("11201")?;
+        assert_eq!("Brooklyn, NY", place.to_string());
+        assert_near(Location::new(40.6940, -73.9903), place.location);
+
+        assert_eq!("Boston, MA", find_zip("02108")?.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_zip_invalid() {
+        assert!(find_zip("1120").is_err());
+        assert!(find_zip("1120a").is_err());
+        assert!(find_zip("00000").is_err());
+    }
+}
@@ -1,14 +1,14 @@
 #![recursion_limit = "256"]
 
-use anyhow::Context;
-use jakesky::ai;
-use jakesky::weather::{ApiKey, WeatherProvider};
-use jakesky::{APP_NAME, alexa};
-use jluszcz_rust_utils::cache::CacheMode;
+use jakesky::APP_NAME;
+use jakesky::alexa::request::RequestEnvelope;
+use jakesky::alexa::verify::verify_application_id;
+use jakesky::skill::{self, SkillConfig};
 use jluszcz_rust_utils::lambda;
 use lambda_runtime::LambdaEvent;
+use log::debug;
+use serde::Deserialize;
 use serde_json::{Value, json};
-use std::env;
 
 #[tokio::main]
 async fn main() -> Result<(), lambda_runtime::Error> {
@@ -28,16 +28,16 @@ async fn function(event: LambdaEvent<Value>) -> Result<Value, lambda_runtime::Er
         return Ok(json!({}));
     }
 
-    let api_key =
-        ApiKey::new(env::var("JAKESKY_API_KEY")?).context("JAKESKY_API_KEY is invalid")?;
-    let latitude = env::var("JAKESKY_LATITUDE")?.parse()?;
-    let longitude = env::var("JAKESKY_LONGITUDE")?.parse()?;
-
-    let report = WeatherProvider::OpenWeather
-        .get_weather(CacheMode::Disabled, &api_key, latitude, longitude)
-        .await?;
-
-    let summarizer = ai::summarizer_for(&report.alerts).await;
-
-    Ok(alexa::forecast(report.weather, report.alerts, summarizer.as_ref()).await?)
+    // Anything else that isn't an Alexa request, e.g. a test event, gets the
+    // forecast as a `LaunchRequest` would.
+    let envelope = match RequestEnvelope::deserialize(&event.payload) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            debug!("Not an Alexa request, reading the forecast: {e}");
+            return Ok(skill::handle_plain(SkillConfig::from_env).await?);
+        }
+    };
+    verify_application_id(&envelope, skill::skill_id_from_env().as_deref())?;
+
+    Ok(skill::handle(&envelope, SkillConfig::from_env).await?)
 }
@@ -1,6 +1,11 @@
 pub mod ai;
 pub mod alert_summary;
 pub mod alexa;
+pub mod config;
+pub mod geocode;
+pub mod skill;
+pub mod ssml;
+pub mod table;
 pub mod weather;
 
 pub const APP_NAME: &str = "jakesky";
@@ -1,11 +1,20 @@
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Result, anyhow};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Parser, ValueEnum};
 use jakesky::ai;
-use jakesky::weather::{ApiKey, WeatherProvider};
-use jakesky::{APP_NAME, alexa};
+use jakesky::alexa::advice::DEFAULT_JACKET_THRESHOLD;
+use jakesky::alexa::{Speech, SpeechOptions};
+use jakesky::config::{Commute, Config};
+use jakesky::weather::open_weather::OpenWeather;
+use jakesky::weather::{
+    ApiKey, FetchOptions, ForecastSource, HourSchedule, Location, ProviderMode, Units,
+    WeatherProvider, WeatherReport, registered_ids,
+};
+use jakesky::{APP_NAME, alexa, geocode, table};
 use jluszcz_rust_utils::cli::VerbosityArgs;
 use jluszcz_rust_utils::{set_up_logger, tls};
 use log::debug;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Debug, Parser)]
@@ -19,19 +28,56 @@ struct Args {
     use_cache: bool,
 
     /// Latitude of location to get weather for
-    #[arg(long, alias = "lat", env = "JAKESKY_LATITUDE", hide_env_values = true)]
-    latitude: f64,
+    #[arg(
+        long,
+        alias = "lat",
+        env = "JAKESKY_LATITUDE",
+        hide_env_values = true,
+        allow_negative_numbers = true,
+        required_unless_present_any = ["locations", "place", "zip", "commute"]
+    )]
+    latitude: Option<f64>,
 
     /// Longitude of location to get weather for
     #[arg(
         long,
         alias = "long",
         env = "JAKESKY_LONGITUDE",
-        hide_env_values = true
+        hide_env_values = true,
+        allow_negative_numbers = true,
+        required_unless_present_any = ["locations", "place", "zip", "commute"]
+    )]
+    longitude: Option<f64>,
+
+    /// US city to get weather for, e.g. "Brooklyn, NY", from the built-in
+    /// gazetteer
+    #[arg(long, conflicts_with_all = ["locations", "zip", "latitude", "longitude"])]
+    place: Option<String>,
+
+    /// US ZIP code to get weather for, from the built-in gazetteer
+    #[arg(long, conflicts_with_all = ["locations", "latitude", "longitude"])]
+    zip: Option<String>,
+
+    /// Named locations from the config file to get weather for, instead of
+    /// --latitude and --longitude
+    #[arg(
+        short = 'l',
+        long = "location",
+        env = "JAKESKY_LOCATION",
+        value_delimiter = ','
     )]
-    longitude: f64,
+    locations: Vec<String>,
+
+    /// Report on the commute from the config file: home when leaving, work
+    /// when heading home
+    #[arg(long, conflicts_with_all = ["locations", "place", "zip"])]
+    commute: bool,
 
-    /// API key to use with the weather provider
+    /// Config file defining named locations [default: ~/.config/jakesky/config.toml]
+    #[arg(long, env = "JAKESKY_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// API key to use with the weather provider, if it requires one
     #[arg(
         short = 'a',
         long,
@@ -39,24 +85,264 @@ struct Args {
         hide_env_values = true,
         value_parser = parse_api_key
     )]
-    api_key: ApiKey,
+    api_key: Option<ApiKey>,
 
-    /// Which weather provider to use (accuweather or openweather)
+    /// Which weather providers to use, in order; later ones are fallbacks
+    /// for when earlier ones fail or time out
     #[arg(
         short = 'p',
+        long = "provider",
+        env = "JAKESKY_PROVIDER",
+        ignore_case = true,
+        value_delimiter = ',',
+        value_parser = provider_parser(),
+        default_value = OpenWeather.id()
+    )]
+    providers: Vec<WeatherProvider>,
+
+    /// Query every provider at once and blend their forecasts, rather than
+    /// falling back from one to the next
+    #[arg(long, env = "JAKESKY_CONSENSUS")]
+    consensus: bool,
+
+    /// Hours of the day (0-23) to report on [default: 8,12,18]
+    #[arg(
+        long,
+        env = "JAKESKY_HOURS",
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(u32).range(0..=23)
+    )]
+    hours: Option<Vec<u32>>,
+
+    /// Hours of the day (0-23) to report on Saturday and Sunday, if different
+    /// from --hours
+    #[arg(
+        long,
+        env = "JAKESKY_WEEKEND_HOURS",
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(u32).range(0..=23)
+    )]
+    weekend_hours: Option<Vec<u32>>,
+
+    /// Also report on 10pm on weekends
+    #[arg(long, env = "JAKESKY_WEEKEND_HOUR")]
+    weekend_hour: bool,
+
+    /// Mention the chance of precipitation once it reaches this percentage
+    #[arg(
         long,
-        value_parser = parse_provider,
-        default_value = WeatherProvider::OpenWeather.id()
+        env = "JAKESKY_PRECIP_THRESHOLD",
+        default_value_t = 30,
+        value_parser = clap::value_parser!(u8).range(0..=100)
     )]
+    precip_threshold: u8,
+
+    /// Suggest an umbrella once the chance of precipitation reaches this
+    /// percentage
+    #[arg(
+        long,
+        env = "JAKESKY_UMBRELLA_THRESHOLD",
+        default_value_t = 50,
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    umbrella_threshold: u8,
+
+    /// Suggest a jacket once it feels colder than this, in the degrees of
+    /// --units [default: 55 imperial, 13 metric]
+    #[arg(long, env = "JAKESKY_JACKET_TEMP", allow_negative_numbers = true)]
+    jacket_temp: Option<f64>,
+
+    /// Speak temperatures in Fahrenheit (imperial) or Celsius (metric)
+    #[arg(
+        long,
+        env = "JAKESKY_UNITS",
+        ignore_case = true,
+        value_parser = units_parser(),
+        default_value = Units::Imperial.id()
+    )]
+    units: Units,
+
+    /// What to print to stdout [default: speech]
+    #[arg(short = 'o', long, env = "JAKESKY_OUTPUT", value_enum)]
+    output: Option<Output>,
+
+    /// Older name for --output, which takes precedence over it
+    #[arg(long, env = "JAKESKY_FORMAT", value_enum, hide = true)]
+    format: Option<Output>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// The forecast as plain sentences
+    #[default]
+    #[value(alias = "text")]
+    Speech,
+    /// The forecast as SSML, as sent to Alexa
+    Ssml,
+    /// The full Alexa skill response
+    AlexaJson,
+    /// Each hour's time, temperature and summary in aligned columns
+    Table,
+    /// The report, in the versioned JSON schema
+    Json,
+}
+
+impl Args {
+    fn schedule(&self) -> HourSchedule {
+        HourSchedule {
+            weekday: self.hours.clone(),
+            weekend: self.weekend_hours.clone(),
+            add_weekend_hour: self.weekend_hour,
+        }
+    }
+
+    /// Each report to print.
+    fn targets(&self) -> Result<Vec<Target>> {
+        let place = match (&self.place, &self.zip) {
+            (Some(place), _) => Some(geocode::find_place(place)?),
+            (_, Some(zip)) => Some(geocode::find_zip(zip)?),
+            _ => None,
+        };
+        if let Some(place) = place {
+            debug!("Resolved {place} to {:?}", place.location);
+            return Ok(vec![Target {
+                name: place.to_string(),
+                provider: self.provider(None, self.schedule()),
+                briefing: Briefing::Forecast(place.location),
+            }]);
+        }
+
+        if self.commute {
+            let commute = self.config()?.commute.ok_or_else(|| {
+                anyhow!("--commute requires a [commute] section in the config file")
+            })?;
+
+            return Ok(vec![Target {
+                name: format!("{} to {}", commute.home.name, commute.work.name),
+                provider: self.provider(commute.home.provider.clone(), commute.schedule()),
+                briefing: Briefing::Commute(Box::new(commute)),
+            }]);
+        }
+
+        if self.locations.is_empty() {
+            let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) else {
+                return Err(anyhow!("--latitude and --longitude are required"));
+            };
+
+            return Ok(vec![Target {
+                name: format!("{latitude},{longitude}"),
+                provider: self.provider(None, self.schedule()),
+                briefing: Briefing::Forecast(Location::new(latitude, longitude)),
+            }]);
+        }
+
+        let config = self.config()?;
+
+        self.locations
+            .iter()
+            .map(|name| {
+                let named = config.location(name)?;
+                Ok(Target {
+                    name: named.name.clone(),
+                    provider: self
+                        .provider(named.provider.clone(), named.schedule(&self.schedule())),
+                    briefing: Briefing::Forecast(named.location),
+                })
+            })
+            .collect()
+    }
+
+    fn config(&self) -> Result<Config> {
+        let path = self
+            .config
+            .clone()
+            .or_else(Config::default_path)
+            .ok_or_else(|| anyhow!("Can't find the config file, use --config"))?;
+
+        Config::load(&path)
+    }
+
+    /// `sources`, or else the --provider chain, with the other provider
+    /// settings applied.
+    fn provider(
+        &self,
+        sources: Option<WeatherProvider>,
+        schedule: HourSchedule,
+    ) -> WeatherProvider {
+        let provider = sources
+            .unwrap_or_else(|| self.providers.iter().cloned().collect())
+            .with_schedule(schedule)
+            .with_units(self.units);
+
+        if self.consensus {
+            provider.with_mode(ProviderMode::Consensus)
+        } else {
+            provider
+        }
+    }
+
+    fn output(&self) -> Output {
+        self.output.or(self.format).unwrap_or_default()
+    }
+
+    fn speech_options(&self) -> SpeechOptions {
+        SpeechOptions {
+            precip_threshold: f64::from(self.precip_threshold) / 100.0,
+            umbrella_threshold: f64::from(self.umbrella_threshold) / 100.0,
+            jacket_threshold: self.jacket_temp.map_or(DEFAULT_JACKET_THRESHOLD, |temp| {
+                self.units.temp_to_fahrenheit(temp)
+            }),
+            ..SpeechOptions::default()
+        }
+    }
+}
+
+/// One report to print, and the provider to fetch it with.
+struct Target {
+    name: String,
     provider: WeatherProvider,
+    briefing: Briefing,
+}
+
+#[derive(Debug)]
+enum Briefing {
+    /// The usual forecast for one place.
+    Forecast(Location),
+    /// Home when leaving for work, and work when heading home.
+    Commute(Box<Commute>),
+}
+
+impl Target {
+    async fn report(&self, options: &FetchOptions) -> Result<WeatherReport> {
+        match &self.briefing {
+            Briefing::Forecast(location) => self.provider.get_weather(options, location).await,
+            Briefing::Commute(commute) => {
+                self.provider
+                    .get_weather_along(options, &commute.itinerary())
+                    .await
+            }
+        }
+    }
+
+    fn commute(&self) -> Option<&Commute> {
+        match &self.briefing {
+            Briefing::Commute(commute) => Some(commute.as_ref()),
+            Briefing::Forecast(_) => None,
+        }
+    }
 }
 
 fn parse_api_key(s: &str) -> Result<ApiKey, String> {
     ApiKey::new(s).map_err(|e| e.to_string())
 }
 
-fn parse_provider(s: &str) -> Result<WeatherProvider, String> {
-    WeatherProvider::from_str(s).map_err(|e| e.to_string())
+/// Accepts any registered provider id, listing them in `--help`.
+fn provider_parser() -> impl TypedValueParser<Value = WeatherProvider> {
+    PossibleValuesParser::new(registered_ids()).try_map(|s| WeatherProvider::from_str(&s))
+}
+
+fn units_parser() -> impl TypedValueParser<Value = Units> {
+    PossibleValuesParser::new(Units::ALL.map(Units::id)).try_map(|s| Units::from_str(&s))
 }
 
 fn parse_args() -> Args {
@@ -71,23 +357,69 @@ async fn main() -> Result<()> {
     set_up_logger(APP_NAME, module_path!(), args.verbosity)?;
     debug!("{args:?}");
 
-    let report = args
-        .provider
-        .get_weather(
-            args.use_cache.into(),
-            &args.api_key,
-            args.latitude,
-            args.longitude,
-        )
-        .await?;
+    let targets = args.targets()?;
+    let speech_options = args.speech_options();
+    let options = FetchOptions::new(args.use_cache.into(), args.api_key.clone());
 
-    let summarizer = ai::summarizer_for(&report.alerts).await;
+    for (index, target) in targets.iter().enumerate() {
+        if targets.len() > 1 {
+            if index > 0 {
+                println!();
+            }
+            println!("==> {} <==", target.name);
+        }
 
-    alexa::forecast(report.weather, report.alerts, summarizer.as_ref()).await?;
+        let report = target.report(&options).await?;
+        print_report(report, target.commute(), args.output(), &speech_options).await?;
+    }
 
     Ok(())
 }
 
+async fn print_report(
+    report: WeatherReport,
+    commute: Option<&Commute>,
+    output: Output,
+    speech_options: &SpeechOptions,
+) -> Result<()> {
+    let speech = |report| speech(report, commute, speech_options);
+
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        Output::Table => print!("{}", table::render(&report)),
+        Output::Speech => println!("{}", speech(report).await?.text()),
+        Output::Ssml => println!("{}", speech(report).await?.ssml()),
+        Output::AlexaJson => {
+            let response = speech(report).await?.response();
+            println!("{}", serde_json::to_string_pretty(&response)?)
+        }
+    }
+
+    Ok(())
+}
+
+async fn speech(
+    report: WeatherReport,
+    commute: Option<&Commute>,
+    options: &SpeechOptions,
+) -> Result<Speech> {
+    let summarizer = ai::summarizer_for(&report.alerts).await;
+
+    match commute {
+        Some(commute) => {
+            alexa::commute_speech(
+                report,
+                commute.depart,
+                commute.return_hour,
+                summarizer.as_ref(),
+                options,
+            )
+            .await
+        }
+        None => alexa::speech(report, summarizer.as_ref(), options).await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,7 +433,7 @@ mod tests {
             "--latitude",
             "40.7128",
             "--longitude",
-            "74.0060",
+            "-74.0060",
             "--api-key",
             "test-key",
         ]
@@ -110,11 +442,31 @@ mod tests {
     /// The real command with env-var support stripped, so ambient JAKESKY_*
     /// variables can't leak into tests.
     fn create_test_command() -> Command {
-        ["latitude", "longitude", "api_key"]
-            .into_iter()
-            .fold(Args::command(), |command, name| {
-                command.mut_arg(name, |arg| arg.env(None::<&'static str>))
-            })
+        [
+            "latitude",
+            "longitude",
+            "locations",
+            "config",
+            "place",
+            "zip",
+            "commute",
+            "api_key",
+            "providers",
+            "consensus",
+            "hours",
+            "weekend_hours",
+            "weekend_hour",
+            "precip_threshold",
+            "umbrella_threshold",
+            "jacket_temp",
+            "units",
+            "output",
+            "format",
+        ]
+        .into_iter()
+        .fold(Args::command(), |command, name| {
+            command.mut_arg(name, |arg| arg.env(None::<&'static str>))
+        })
     }
 
     fn parse_args_from(args: &[&str]) -> Result<Args, clap::Error> {
@@ -122,6 +474,10 @@ mod tests {
         Args::from_arg_matches(&matches)
     }
 
+    fn provider_ids(args: &Args) -> Vec<&'static str> {
+        args.providers.iter().flat_map(|p| p.ids()).collect()
+    }
+
     #[test]
     fn test_parse_args_minimal() {
         let args = parse_args_from(&base_args()).unwrap();
@@ -131,9 +487,9 @@ mod tests {
             CacheMode::from(args.use_cache),
             CacheMode::Disabled
         ));
-        assert_eq!(args.provider.id(), WeatherProvider::OpenWeather.id());
-        assert_eq!(args.latitude, 40.7128);
-        assert_eq!(args.longitude, 74.0060);
+        assert_eq!(provider_ids(&args), vec![OpenWeather.id()]);
+        assert_eq!(args.latitude, Some(40.7128));
+        assert_eq!(args.longitude, Some(-74.0060));
     }
 
     #[test]
@@ -163,7 +519,7 @@ mod tests {
         args.extend_from_slice(&["--provider", "accuweather"]);
         let args = parse_args_from(&args).unwrap();
 
-        assert_eq!(args.provider.id(), WeatherProvider::AccuWeather.id());
+        assert_eq!(provider_ids(&args), vec!["accuweather"]);
     }
 
     #[test]
@@ -173,7 +529,7 @@ mod tests {
             "--lat",
             "40.7128",
             "--long",
-            "74.0060",
+            "-74.0060",
             "-a",
             "test-key",
             "-p",
@@ -181,15 +537,169 @@ mod tests {
         ])
         .unwrap();
 
-        assert_eq!(args.latitude, 40.7128);
-        assert_eq!(args.longitude, 74.0060);
-        assert_eq!(args.provider.id(), WeatherProvider::OpenWeather.id());
+        assert_eq!(args.latitude, Some(40.7128));
+        assert_eq!(args.longitude, Some(-74.0060));
+        assert_eq!(provider_ids(&args), vec![OpenWeather.id()]);
+    }
+
+    #[test]
+    fn test_parse_args_locations() {
+        let args =
+            parse_args_from(&["jakesky", "--location", "home,office", "-l", "cabin"]).unwrap();
+
+        assert_eq!(vec!["home", "office", "cabin"], args.locations);
+        assert_eq!(None, args.latitude);
+        assert_eq!(None, args.longitude);
+    }
+
+    #[test]
+    fn test_parse_args_negative_coordinates() {
+        let args =
+            parse_args_from(&["jakesky", "--lat", "-33.8688", "--long", "-151.2093"]).unwrap();
+
+        assert_eq!(args.latitude, Some(-33.8688));
+        assert_eq!(args.longitude, Some(-151.2093));
+    }
+
+    fn forecast_location(target: &Target) -> Location {
+        match target.briefing {
+            Briefing::Forecast(location) => location,
+            ref briefing => panic!("Expected a forecast, got {briefing:?}"),
+        }
+    }
+
+    /// Targets for `args` plus `--config` pointing at a file containing `toml`.
+    fn targets_with_config(test: &str, toml: &str, args: &[&str]) -> Result<Vec<Target>> {
+        let path = std::env::temp_dir().join(format!("jakesky-{test}-{}.toml", std::process::id()));
+        std::fs::write(&path, toml)?;
+
+        let mut args = args.to_vec();
+        args.extend_from_slice(&["--config", path.to_str().unwrap()]);
+        let targets = parse_args_from(&args)
+            .map_err(anyhow::Error::from)
+            .and_then(|args| args.targets());
+
+        std::fs::remove_file(&path)?;
+        targets
+    }
+
+    #[test]
+    fn test_targets_from_place() {
+        let args = parse_args_from(&["jakesky", "--place", "Brooklyn, NY"]).unwrap();
+        let targets = args.targets().unwrap();
+
+        assert_eq!(1, targets.len());
+        assert_eq!("Brooklyn, NY", targets[0].name);
+        assert_eq!(
+            geocode::find_place("Brooklyn, NY").unwrap().location,
+            forecast_location(&targets[0])
+        );
+    }
+
+    #[test]
+    fn test_targets_from_zip() {
+        let args = parse_args_from(&["jakesky", "--zip", "11201"]).unwrap();
+        let targets = args.targets().unwrap();
+
+        assert_eq!("Brooklyn, NY", targets[0].name);
+        assert_eq!(
+            geocode::find_zip("11201").unwrap().location,
+            forecast_location(&targets[0])
+        );
+    }
+
+    #[test]
+    fn test_parse_args_place_conflicts_with_zip() {
+        assert!(
+            parse_args_from(&["jakesky", "--place", "Brooklyn, NY", "--zip", "11201"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_args_place_and_zip_conflict_with_coordinates() {
+        assert!(
+            parse_args_from(&["jakesky", "--place", "Brooklyn, NY", "--latitude", "40.7"]).is_err()
+        );
+        assert!(parse_args_from(&["jakesky", "--zip", "11201", "--longitude", "-74.0"]).is_err());
+    }
+
+    #[test]
+    fn test_targets_from_config() -> Result<()> {
+        let targets = targets_with_config(
+            "locations",
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+
+            [locations.cabin]
+            latitude = 44.2706
+            longitude = -71.3033
+            provider = "nws"
+            "#,
+            &["jakesky", "--location", "cabin,home"],
+        )?;
+
+        let names: Vec<_> = targets.iter().map(|target| target.name.as_str()).collect();
+        assert_eq!(vec!["cabin", "home"], names);
+        assert_eq!(
+            Location::new(44.2706, -71.3033),
+            forecast_location(&targets[0])
+        );
+        assert_eq!(vec!["nws"], targets[0].provider.ids());
+        assert_eq!(vec![OpenWeather.id()], targets[1].provider.ids());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_targets_for_commute() -> Result<()> {
+        let targets = targets_with_config(
+            "commute",
+            r#"
+            [locations.home]
+            latitude = 40.6943
+            longitude = -73.9249
+
+            [locations.office]
+            latitude = 40.7527
+            longitude = -73.9772
+
+            [commute]
+            home = "home"
+            work = "office"
+            depart = 8
+            return = 18
+            "#,
+            &["jakesky", "--commute"],
+        )?;
+
+        assert_eq!(1, targets.len());
+        assert_eq!("home to office", targets[0].name);
+        let commute = targets[0].commute().expect("a commute");
+        assert_eq!((8, 18), (commute.depart, commute.return_hour));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_targets_for_commute_without_config_section() {
+        let e = targets_with_config("no-commute", "", &["jakesky", "--commute"])
+            .err()
+            .unwrap();
+
+        assert!(e.to_string().contains("[commute]"), "{e}");
     }
 
     #[test]
     fn test_parse_args_missing_required_latitude() {
-        let result =
-            parse_args_from(&["jakesky", "--longitude", "74.0060", "--api-key", "test-key"]);
+        let result = parse_args_from(&[
+            "jakesky",
+            "--longitude",
+            "-74.0060",
+            "--api-key",
+            "test-key",
+        ]);
         assert!(result.is_err());
     }
 
@@ -201,15 +711,189 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_args_missing_required_api_key() {
-        let result = parse_args_from(&[
+    fn test_parse_args_without_api_key() {
+        let args = parse_args_from(&[
             "jakesky",
             "--latitude",
             "40.7128",
             "--longitude",
             "-74.0060",
+            "--provider",
+            "openmeteo",
+        ])
+        .unwrap();
+
+        assert!(args.api_key.is_none());
+        assert_eq!(provider_ids(&args), vec!["openmeteo"]);
+    }
+
+    #[test]
+    fn test_parse_args_with_fallback_providers() {
+        let mut args = base_args();
+        args.extend_from_slice(&["--provider", "openweather,accuweather,nws"]);
+        let args = parse_args_from(&args).unwrap();
+
+        assert_eq!(
+            provider_ids(&args),
+            vec!["openweather", "accuweather", "nws"]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_consensus() {
+        let args = parse_args_from(&base_args()).unwrap();
+        assert!(!args.consensus);
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--provider", "openweather,nws", "--consensus"]);
+        let args = parse_args_from(&args).unwrap();
+
+        assert!(args.consensus);
+        assert_eq!(provider_ids(&args), vec!["openweather", "nws"]);
+    }
+
+    #[test]
+    fn test_parse_args_default_schedule() {
+        let args = parse_args_from(&base_args()).unwrap();
+
+        assert_eq!(args.schedule(), HourSchedule::default());
+    }
+
+    #[test]
+    fn test_parse_args_with_schedule() {
+        let mut args = base_args();
+        args.extend_from_slice(&[
+            "--hours",
+            "7,12,17",
+            "--weekend-hours",
+            "10,14",
+            "--weekend-hour",
         ]);
-        assert!(result.is_err());
+        let args = parse_args_from(&args).unwrap();
+
+        assert_eq!(
+            args.schedule(),
+            HourSchedule {
+                weekday: Some(vec![7, 12, 17]),
+                weekend: Some(vec![10, 14]),
+                add_weekend_hour: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_hours_out_of_range() {
+        let mut args = base_args();
+        args.extend_from_slice(&["--hours", "7,24"]);
+
+        assert!(parse_args_from(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_precip_threshold() {
+        let args = parse_args_from(&base_args()).unwrap();
+        assert_eq!(args.speech_options(), SpeechOptions::default());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--precip-threshold", "50"]);
+        let args = parse_args_from(&args).unwrap();
+        assert_eq!(0.5, args.speech_options().precip_threshold);
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--precip-threshold", "101"]);
+        assert!(parse_args_from(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_advice_thresholds() {
+        let mut args = base_args();
+        args.extend_from_slice(&["--umbrella-threshold", "70", "--jacket-temp", "-5"]);
+        let options = parse_args_from(&args).unwrap().speech_options();
+        assert_eq!(0.7, options.umbrella_threshold);
+        assert_eq!(-5.0, options.jacket_threshold);
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--units", "metric", "--jacket-temp", "10"]);
+        assert_eq!(
+            50.0,
+            parse_args_from(&args)
+                .unwrap()
+                .speech_options()
+                .jacket_threshold
+        );
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--units", "metric"]);
+        assert_eq!(
+            DEFAULT_JACKET_THRESHOLD,
+            parse_args_from(&args)
+                .unwrap()
+                .speech_options()
+                .jacket_threshold
+        );
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--umbrella-threshold", "101"]);
+        assert!(parse_args_from(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_units() {
+        let args = parse_args_from(&base_args()).unwrap();
+        assert_eq!(Units::Imperial, args.units);
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--units", "Metric"]);
+        let args = parse_args_from(&args).unwrap();
+        assert_eq!(Units::Metric, args.units);
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--units", "kelvin"]);
+        assert!(parse_args_from(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_output() {
+        let args = parse_args_from(&base_args()).unwrap();
+        assert_eq!(Output::Speech, args.output());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--output", "alexa-json"]);
+        let args = parse_args_from(&args).unwrap();
+        assert_eq!(Output::AlexaJson, args.output());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["-o", "table"]);
+        let args = parse_args_from(&args).unwrap();
+        assert_eq!(Output::Table, args.output());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--output", "xml"]);
+        assert!(parse_args_from(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_format_is_older_name_for_output() {
+        let mut args = base_args();
+        args.extend_from_slice(&["--format", "json"]);
+        assert_eq!(Output::Json, parse_args_from(&args).unwrap().output());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--format", "text"]);
+        assert_eq!(Output::Speech, parse_args_from(&args).unwrap().output());
+
+        let mut args = base_args();
+        args.extend_from_slice(&["--format", "json", "--output", "table"]);
+        assert_eq!(Output::Table, parse_args_from(&args).unwrap().output());
+    }
+
+    #[test]
+    fn test_parse_args_provider_ignores_case() {
+        let mut args = base_args();
+        args.extend_from_slice(&["--provider", "AccuWeather"]);
+        let args = parse_args_from(&args).unwrap();
+
+        assert_eq!(provider_ids(&args), vec!["accuweather"]);
     }
 
     #[test]
@@ -228,7 +912,7 @@ mod tests {
             "--latitude",
             "invalid",
             "--longitude",
-            "74.0060",
+            "-74.0060",
             "--api-key",
             "test-key",
         ]);
@@ -0,0 +1,288 @@
+use anyhow::{Context, Result, anyhow};
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use clap::Parser;
+use jakesky::APP_NAME;
+use jakesky::alexa::verify::{CERT_CHAIN_URL_HEADER, RequestVerifier, SIGNATURE_HEADER};
+use jakesky::skill::{self, SkillConfig};
+use jakesky::weather::{Location, WeatherReport, validate_coordinates};
+use jluszcz_rust_utils::cli::VerbosityArgs;
+use jluszcz_rust_utils::{set_up_logger, tls};
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+#[derive(Debug, Parser)]
+#[command(name = "JakeSky-rs server", version, author, infer_long_args = true)]
+struct Args {
+    #[command(flatten)]
+    verbosity: VerbosityArgs,
+
+    /// Address to listen on
+    #[arg(long, env = "JAKESKY_ADDRESS", default_value = "0.0.0.0:8080")]
+    address: SocketAddr,
+
+    /// Require this bearer token on /forecast requests
+    #[arg(long, env = "JAKESKY_FORECAST_TOKEN", hide_env_values = true)]
+    forecast_token: Option<String>,
+}
+
+struct AppState {
+    config: SkillConfig,
+    verifier: RequestVerifier,
+    /// `/forecast` requires `Authorization: Bearer <token>`, when set.
+    forecast_token: Option<String>,
+}
+
+/// An error response: the status to reply with and the cause, which is only
+/// logged. Callers get just the status's reason, since causes can include
+/// provider URLs and their API keys.
+struct AppError(StatusCode, anyhow::Error);
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let AppError(status, e) = self;
+        warn!("{status}: {e:#}");
+        (status, status.canonical_reason().unwrap_or_default()).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastQuery {
+    lat: f64,
+    lon: f64,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tls::install_default_provider();
+
+    let args = Args::parse();
+    set_up_logger(APP_NAME, module_path!(), args.verbosity)?;
+    debug!("{args:?}");
+
+    let config = SkillConfig::from_env()?;
+    let verifier = RequestVerifier::new(config.skill_id.clone());
+    if args.forecast_token.is_none() {
+        warn!("/forecast is open to anyone who can reach the server");
+    }
+    let app = router(AppState {
+        config,
+        verifier,
+        forecast_token: args.forecast_token,
+    });
+
+    let listener = TcpListener::bind(args.address)
+        .await
+        .with_context(|| format!("Failed to listen on {}", args.address))?;
+    info!("Listening on {}", listener.local_addr()?);
+
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/alexa", post(alexa))
+        .route("/forecast", get(forecast))
+        .with_state(Arc::new(state))
+}
+
+/// Answers an Alexa request, after checking its signature.
+async fn alexa(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, AppError> {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AppError(StatusCode::BAD_REQUEST, anyhow!("Missing {name} header")))
+    };
+    let cert_chain_url = header(CERT_CHAIN_URL_HEADER)?;
+    let signature = header(SIGNATURE_HEADER)?;
+
+    let envelope = state
+        .verifier
+        .verify(cert_chain_url, signature, &body)
+        .await
+        .map_err(|e| AppError(StatusCode::BAD_REQUEST, e))?;
+
+    skill::handle(&envelope, || Ok(&state.config))
+        .await
+        .map(Json)
+        .map_err(|e| AppError(StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// Returns the report for the given coordinates, using the configured
+/// provider and hours, once the caller presents the forecast token if one is
+/// set.
+async fn forecast(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ForecastQuery>,
+) -> Result<Json<WeatherReport>, AppError> {
+    if let Some(token) = &state.forecast_token {
+        let presented = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if !presented.is_some_and(|presented| constant_time_eq(presented, token)) {
+            return Err(AppError(
+                StatusCode::UNAUTHORIZED,
+                anyhow!("Missing or incorrect forecast token"),
+            ));
+        }
+    }
+
+    validate_coordinates(query.lat, query.lon).map_err(|e| AppError(StatusCode::BAD_REQUEST, e))?;
+
+    state
+        .config
+        .provider
+        .get_weather(
+            &state.config.fetch_options,
+            &Location::new(query.lat, query.lon),
+        )
+        .await
+        .map(Json)
+        .map_err(|e| AppError(StatusCode::BAD_GATEWAY, e))
+}
+
+/// Compares without returning early, so the time taken doesn't reveal how
+/// much of the token matched.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use jakesky::alexa::SpeechOptions;
+    use jakesky::weather::open_weather::OpenWeather;
+    use jakesky::weather::{FetchOptions, WeatherProvider};
+    use jluszcz_rust_utils::cache::CacheMode;
+    use tower::ServiceExt;
+
+    fn test_router() -> Router {
+        test_router_with_token(None)
+    }
+
+    fn test_router_with_token(forecast_token: Option<&str>) -> Router {
+        router(AppState {
+            config: SkillConfig {
+                provider: WeatherProvider::new(OpenWeather),
+                speech_options: SpeechOptions::default(),
+                fetch_options: FetchOptions::new(CacheMode::Disabled, None),
+                location: Location::new(40.7, -74.0),
+                skill_id: None,
+            },
+            verifier: RequestVerifier::new(None),
+            forecast_token: forecast_token.map(String::from),
+        })
+    }
+
+    async fn status(request: Request<Body>) -> StatusCode {
+        test_router().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_alexa_requires_signature() {
+        let request = Request::post("/alexa").body(Body::from("{}")).unwrap();
+
+        assert_eq!(StatusCode::BAD_REQUEST, status(request).await);
+    }
+
+    #[tokio::test]
+    async fn test_alexa_rejects_untrusted_cert_chain_url() {
+        let request = Request::post("/alexa")
+            .header(
+                CERT_CHAIN_URL_HEADER,
+                "https://example.com/echo.api/cert.pem",
+            )
+            .header(SIGNATURE_HEADER, "c2lnbmF0dXJl")
+            .body(Body::from("{}"))
+            .unwrap();
+
+        assert_eq!(StatusCode::BAD_REQUEST, status(request).await);
+    }
+
+    #[tokio::test]
+    async fn test_errors_do_not_leak_causes() {
+        let request = Request::post("/alexa")
+            .header(
+                CERT_CHAIN_URL_HEADER,
+                "https://example.com/echo.api/cert.pem",
+            )
+            .header(SIGNATURE_HEADER, "c2lnbmF0dXJl")
+            .body(Body::from("{}"))
+            .unwrap();
+
+        let response = test_router().oneshot(request).await.unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!("Bad Request", body);
+    }
+
+    #[tokio::test]
+    async fn test_forecast_requires_token_when_set() {
+        let router = test_router_with_token(Some("s3cret"));
+
+        for authorization in [None, Some("Bearer wrong"), Some("s3cret")] {
+            let mut request = Request::get("/forecast?lat=91&lon=0");
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            let response = router
+                .clone()
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        }
+
+        // With the token, the request gets as far as checking coordinates.
+        let request = Request::get("/forecast?lat=91&lon=0")
+            .header(AUTHORIZATION, "Bearer s3cret")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_forecast_rejects_invalid_coordinates() {
+        let request = Request::get("/forecast?lat=91&lon=0")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(StatusCode::BAD_REQUEST, status(request).await);
+    }
+
+    #[tokio::test]
+    async fn test_forecast_requires_coordinates() {
+        let request = Request::get("/forecast?lat=40.7")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(StatusCode::BAD_REQUEST, status(request).await);
+    }
+}
@@ -0,0 +1,239 @@
+//! Answers Alexa requests the same way however they arrive, whether from
+//! Lambda or the standalone HTTP server.
+
+use crate::ai;
+use crate::alexa::request::{RequestEnvelope, Route};
+use crate::alexa::{self, SpeechOptions};
+use crate::weather::open_weather::OpenWeather;
+use crate::weather::{
+    ApiKey, FetchOptions, HourSchedule, Location, ProviderMode, Units, WeatherProvider, parse_hours,
+};
+use anyhow::{Context, Result, anyhow};
+use jluszcz_rust_utils::cache::CacheMode;
+use log::info;
+use serde_json::Value;
+use std::borrow::Borrow;
+use std::env;
+
+/// Everything needed to answer a request, read from `JAKESKY_*` environment
+/// variables.
+#[derive(Clone)]
+pub struct SkillConfig {
+    pub provider: WeatherProvider,
+    pub speech_options: SpeechOptions,
+    pub fetch_options: FetchOptions,
+    pub location: Location,
+    /// Requests for any other skill are rejected, when set.
+    pub skill_id: Option<String>,
+}
+
+impl SkillConfig {
+    pub fn from_env() -> Result<Self> {
+        let provider = match env::var("JAKESKY_PROVIDER") {
+            Ok(provider) => provider.parse().context("JAKESKY_PROVIDER is invalid")?,
+            Err(_) => WeatherProvider::new(OpenWeather),
+        };
+        let provider = match env::var("JAKESKY_CONSENSUS") {
+            Ok(consensus) if consensus.parse().context("JAKESKY_CONSENSUS is invalid")? => {
+                provider.with_mode(ProviderMode::Consensus)
+            }
+            _ => provider,
+        };
+        let schedule = HourSchedule {
+            weekday: env::var("JAKESKY_HOURS")
+                .ok()
+                .map(|hours| parse_hours(&hours))
+                .transpose()
+                .context("JAKESKY_HOURS is invalid")?,
+            weekend: env::var("JAKESKY_WEEKEND_HOURS")
+                .ok()
+                .map(|hours| parse_hours(&hours))
+                .transpose()
+                .context("JAKESKY_WEEKEND_HOURS is invalid")?,
+            add_weekend_hour: match env::var("JAKESKY_WEEKEND_HOUR") {
+                Ok(weekend_hour) => weekend_hour
+                    .parse()
+                    .context("JAKESKY_WEEKEND_HOUR is invalid")?,
+                Err(_) => false,
+            },
+        };
+        let units = match env::var("JAKESKY_UNITS") {
+            Ok(units) => units.parse().context("JAKESKY_UNITS is invalid")?,
+            Err(_) => Units::default(),
+        };
+        let provider = provider.with_schedule(schedule).with_units(units);
+        let mut speech_options = SpeechOptions::default();
+        if let Ok(threshold) = env::var("JAKESKY_PRECIP_THRESHOLD") {
+            speech_options.precip_threshold =
+                parse_percent(&threshold).context("JAKESKY_PRECIP_THRESHOLD is invalid")?;
+        }
+        if let Ok(threshold) = env::var("JAKESKY_UMBRELLA_THRESHOLD") {
+            speech_options.umbrella_threshold =
+                parse_percent(&threshold).context("JAKESKY_UMBRELLA_THRESHOLD is invalid")?;
+        }
+        if let Ok(temp) = env::var("JAKESKY_JACKET_TEMP") {
+            let temp = temp
+                .trim()
+                .parse()
+                .context("JAKESKY_JACKET_TEMP is invalid")?;
+            speech_options.jacket_threshold = units.temp_to_fahrenheit(temp);
+        }
+        let api_key = env::var("JAKESKY_API_KEY")
+            .ok()
+            .map(ApiKey::new)
+            .transpose()
+            .context("JAKESKY_API_KEY is invalid")?;
+        let location = Location::new(
+            env::var("JAKESKY_LATITUDE")
+                .context("JAKESKY_LATITUDE is not set")?
+                .parse()
+                .context("JAKESKY_LATITUDE is invalid")?,
+            env::var("JAKESKY_LONGITUDE")
+                .context("JAKESKY_LONGITUDE is not set")?
+                .parse()
+                .context("JAKESKY_LONGITUDE is invalid")?,
+        );
+
+        Ok(Self {
+            provider,
+            speech_options,
+            fetch_options: FetchOptions::new(CacheMode::Disabled, api_key),
+            location,
+            skill_id: skill_id_from_env(),
+        })
+    }
+}
+
+/// `JAKESKY_SKILL_ID`, which needs none of the weather settings, so requests
+/// can be checked before they're read.
+pub fn skill_id_from_env() -> Option<String> {
+    env::var("JAKESKY_SKILL_ID").ok()
+}
+
+/// Routes a request to the matching response. The config is only loaded, and
+/// weather only fetched, when the response needs it, so help and goodbyes
+/// still work if the weather settings are missing or invalid.
+pub async fn handle<C, F>(envelope: &RequestEnvelope, load_config: F) -> Result<Value>
+where
+    C: Borrow<SkillConfig>,
+    F: FnOnce() -> Result<C>,
+{
+    match envelope.request.route() {
+        route @ (Route::Forecast | Route::ForecastForDate(_) | Route::ForecastForTime(_)) => {
+            forecast(route, load_config()?.borrow()).await
+        }
+        Route::Help => Ok(alexa::help()),
+        Route::Stop => Ok(alexa::goodbye()),
+        Route::Ignore => {
+            info!("Ignoring request: {:?}", envelope.request);
+            Ok(alexa::empty())
+        }
+    }
+}
+
+/// Reads the forecast, as for a `LaunchRequest`, for invocations that aren't
+/// Alexa requests, e.g. test events.
+pub async fn handle_plain<C, F>(load_config: F) -> Result<Value>
+where
+    C: Borrow<SkillConfig>,
+    F: FnOnce() -> Result<C>,
+{
+    forecast(Route::Forecast, load_config()?.borrow()).await
+}
+
+async fn forecast(route: Route, config: &SkillConfig) -> Result<Value> {
+    let SkillConfig {
+        provider,
+        speech_options,
+        fetch_options,
+        location,
+        ..
+    } = config;
+
+    match route {
+        Route::ForecastForDate(date) => {
+            let report = provider
+                .get_weather_for_date(fetch_options, location, date)
+                .await?;
+            let summarizer = ai::summarizer_for(&report.alerts).await;
+
+            alexa::forecast_for_date(report, date, summarizer.as_ref(), speech_options).await
+        }
+        Route::ForecastForTime(time) => {
+            let report = provider.get_weather(fetch_options, location).await?;
+
+            alexa::forecast_for_time(report, time, speech_options)
+        }
+        _ => {
+            let report = provider.get_weather(fetch_options, location).await?;
+            let summarizer = ai::summarizer_for(&report.alerts).await;
+
+            alexa::forecast(report, summarizer.as_ref(), speech_options).await
+        }
+    }
+}
+
+/// Parses a whole-number percentage, 0–100, into a fraction.
+fn parse_percent(s: &str) -> Result<f64> {
+    match s.trim().parse::<u8>() {
+        Ok(percent) if percent <= 100 => Ok(f64::from(percent) / 100.0),
+        _ => Err(anyhow!(
+            "Expected a percentage between 0 and 100, got: '{s}'"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HELP_REQUEST: &str = r#"{"version":"1.0","request":{"type":"IntentRequest","requestId":"amzn1.echo-api.request.2222","timestamp":"2024-01-15T12:00:05Z","intent":{"name":"AMAZON.HelpIntent","confirmationStatus":"NONE"}}}"#;
+
+    #[tokio::test]
+    async fn test_handle_help_without_config() -> Result<()> {
+        let envelope: RequestEnvelope = serde_json::from_str(HELP_REQUEST)?;
+
+        let response = handle(&envelope, || -> Result<SkillConfig> {
+            Err(anyhow!("JAKESKY_LATITUDE is not set"))
+        })
+        .await?;
+        assert_eq!(alexa::help(), response);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_forecast_needs_config() -> Result<()> {
+        let envelope: RequestEnvelope =
+            serde_json::from_str(&HELP_REQUEST.replace("AMAZON.HelpIntent", "GetForecastIntent"))?;
+
+        let e = handle(&envelope, || -> Result<SkillConfig> {
+            Err(anyhow!("JAKESKY_LATITUDE is not set"))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!("JAKESKY_LATITUDE is not set", e.to_string());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_plain_needs_config() {
+        let e =
+            handle_plain(|| -> Result<SkillConfig> { Err(anyhow!("JAKESKY_LATITUDE is not set")) })
+                .await
+                .unwrap_err();
+        assert_eq!("JAKESKY_LATITUDE is not set", e.to_string());
+    }
+
+    #[test]
+    fn test_parse_percent() -> Result<()> {
+        assert_eq!(0.0, parse_percent("0")?);
+        assert_eq!(1.0, parse_percent("100")?);
+        assert_eq!(0.3, parse_percent(" 30 ")?);
+        assert!(parse_percent("101").is_err());
+        assert!(parse_percent("abc").is_err());
+
+        Ok(())
+    }
+}
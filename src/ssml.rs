@@ -0,0 +1,139 @@
+//! Render forecast sentences as SSML, so Alexa pauses between sentences and
+//! before alerts rather than reading everything run together.
+
+/// Pause before the alert section.
+const ALERT_BREAK: &str = r#"<break time="500ms"/>"#;
+
+/// Wraps each sentence in `<s>`, inserts a pause before the sentence at
+/// `alerts_at` (the first one about alerts, if any), and marks times like
+/// "8am" with `<say-as interpret-as="time">`.
+pub fn render(sentences: &[String], alerts_at: Option<usize>) -> String {
+    let mut ssml = String::from("<speak>");
+
+    for (index, sentence) in sentences.iter().enumerate() {
+        if Some(index) == alerts_at {
+            ssml.push_str(ALERT_BREAK);
+        }
+
+        ssml.push_str("<s>");
+        ssml.push_str(&mark_times(&escape(sentence)));
+        ssml.push_str("</s>");
+    }
+
+    ssml.push_str("</speak>");
+    ssml
+}
+
+/// Escapes characters that are special in XML, e.g. the ampersands NWS uses
+/// in alert text.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Wraps each word that's a clock time, e.g. "8am" or "11pm," in `<say-as>`,
+/// leaving any trailing punctuation outside the tag.
+fn mark_times(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let time = word.trim_end_matches([',', '.']);
+            if is_time(time) {
+                format!(
+                    r#"<say-as interpret-as="time">{time}</say-as>{}"#,
+                    &word[time.len()..]
+                )
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Matches the hour format used when speaking timestamps, e.g. "8am".
+fn is_time(word: &str) -> bool {
+    let Some(hour) = word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) else {
+        return false;
+    };
+
+    matches!(hour.parse::<u32>(), Ok(1..=12)) && hour.len() <= 2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            "Wind &amp; Snow &lt;Advisory&gt; &quot;until&quot; noon&apos;s end",
+            escape(r#"Wind & Snow <Advisory> "until" noon's end"#)
+        );
+    }
+
+    #[test]
+    fn test_is_time() {
+        assert!(is_time("8am"));
+        assert!(is_time("12pm"));
+        assert!(!is_time("13pm"));
+        assert!(!is_time("0am"));
+        assert!(!is_time("am"));
+        assert!(!is_time("spam"));
+        assert!(!is_time("noon"));
+    }
+
+    #[test]
+    fn test_mark_times() {
+        assert_eq!(
+            r#"At <say-as interpret-as="time">8am</say-as>, it will be 72 and sunny."#,
+            mark_times("At 8am, it will be 72 and sunny.")
+        );
+        assert_eq!(
+            r#"until <say-as interpret-as="time">6pm</say-as>."#,
+            mark_times("until 6pm.")
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let sentences = vec![
+            "It's currently 72 and sunny.".to_string(),
+            "At 6pm, it will be 65 and cloudy.".to_string(),
+            "There is a flood & wind watch until 8pm tomorrow.".to_string(),
+        ];
+
+        assert_eq!(
+            concat!(
+                "<speak>",
+                "<s>It&apos;s currently 72 and sunny.</s>",
+                r#"<s>At <say-as interpret-as="time">6pm</say-as>, it will be 65 and cloudy.</s>"#,
+                r#"<break time="500ms"/>"#,
+                r#"<s>There is a flood &amp; wind watch until <say-as interpret-as="time">8pm</say-as> tomorrow.</s>"#,
+                "</speak>",
+            ),
+            render(&sentences, Some(2))
+        );
+    }
+
+    #[test]
+    fn test_render_without_alerts() {
+        let sentences = vec!["It's currently 72 and sunny.".to_string()];
+
+        assert_eq!(
+            "<speak><s>It&apos;s currently 72 and sunny.</s></speak>",
+            render(&sentences, None)
+        );
+    }
+}
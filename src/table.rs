@@ -0,0 +1,138 @@
+//! Render a report as a Markdown table, one row per hour, for terminals and
+//! scripts rather than speech.
+
+use crate::weather::{Units, Weather, WeatherReport, whole_degrees};
+
+const HEADERS: [&str; 4] = ["Time", "Temp", "Feels Like", "Summary"];
+
+/// Renders the current conditions and each scheduled hour, with every column
+/// padded to line up.
+pub fn render(report: &WeatherReport) -> String {
+    let rows: Vec<[String; 4]> = report
+        .weather
+        .iter()
+        .enumerate()
+        .map(|(index, weather)| row(weather, index == 0))
+        .collect();
+
+    let widths: [usize; 4] = std::array::from_fn(|column| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .chain([HEADERS[column].len()])
+            .max()
+            .unwrap_or_default()
+    });
+
+    let mut table = format_row(&HEADERS.map(String::from), &widths);
+    table.push_str(&format_row(&widths.map(|width| "-".repeat(width)), &widths));
+    for row in &rows {
+        table.push_str(&format_row(row, &widths));
+    }
+
+    table
+}
+
+fn row(weather: &Weather, is_current: bool) -> [String; 4] {
+    let time = if is_current {
+        "Now".to_string()
+    } else {
+        weather.timestamp.format("%a %-I%P").to_string()
+    };
+
+    [
+        time,
+        format_temp(weather.temp, weather.units),
+        weather
+            .apparent_temp
+            .map(|temp| format_temp(temp, weather.units))
+            .unwrap_or_else(|| "-".to_string()),
+        escape_cell(&weather.summary),
+    ]
+}
+
+fn format_row(cells: &[String; 4], widths: &[usize; 4]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect();
+
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn format_temp(temp: f64, units: Units) -> String {
+    let symbol = match units {
+        Units::Imperial => "°F",
+        Units::Metric => "°C",
+    };
+
+    format!("{}{symbol}", whole_degrees(temp))
+}
+
+/// Escapes pipes, which would otherwise end the cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Tz;
+
+    #[test]
+    fn test_render() {
+        let at = |hour| {
+            Tz::America__New_York
+                .with_ymd_and_hms(2024, 6, 1, hour, 0, 0)
+                .unwrap()
+        };
+
+        let report = WeatherReport {
+            weather: vec![
+                Weather {
+                    timestamp: at(7),
+                    apparent_temp: Some(70.4),
+                    ..Weather::test(Some("Clear"))
+                },
+                Weather {
+                    timestamp: at(12),
+                    temp: 81.6,
+                    ..Weather::test(Some("Partly Cloudy"))
+                },
+                Weather {
+                    timestamp: at(18),
+                    temp: -4.0,
+                    apparent_temp: Some(-11.0),
+                    ..Weather::test(Some("Snow"))
+                },
+            ],
+            hourly: Vec::new(),
+            daily: Vec::new(),
+            alerts: Vec::new(),
+        };
+
+        assert_eq!(
+            concat!(
+                "| Time     | Temp | Feels Like | Summary       |\n",
+                "| -------- | ---- | ---------- | ------------- |\n",
+                "| Now      | 72°F | 70°F       | Clear         |\n",
+                "| Sat 12pm | 81°F | -          | Partly Cloudy |\n",
+                "| Sat 6pm  | -4°F | -11°F      | Snow          |\n",
+            ),
+            render(&report)
+        );
+    }
+
+    #[test]
+    fn test_format_temp() {
+        assert_eq!("21°C", format_temp(21.6, Units::Metric));
+        assert_eq!("-4°F", format_temp(-4.6, Units::Imperial));
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!("Rain \\| Snow", escape_cell("Rain | Snow"));
+        assert_eq!("Clear", escape_cell("Clear"));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::weather::{ApiKey, Weather, WeatherForecast};
+use crate::weather::{
+    ApiKey, DailyWeather, FetchOptions, ForecastFuture, ForecastSource, Location, Units, Weather,
+    WeatherAlert, WeatherForecast, alerts_or_empty, filter_alerts,
+};
 use anyhow::{Context, Result, anyhow};
 use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
@@ -34,16 +37,22 @@ struct CurrentConditionsResponse {
     weather: String,
 
     #[serde(alias = "Temperature")]
-    temp: ImperialTemperature,
+    temp: ImperialMeasurement,
 
     #[serde(default, alias = "RealFeelTemperature")]
-    feels_like_temp: Option<ImperialTemperature>,
+    feels_like_temp: Option<ImperialMeasurement>,
+
+    #[serde(default, alias = "Wind")]
+    wind: Option<Wind<ImperialMeasurement>>,
+
+    #[serde(default, alias = "WindGust")]
+    wind_gust: Option<Wind<ImperialMeasurement>>,
 }
 
 #[derive(Deserialize, Debug)]
-struct ImperialTemperature {
+struct ImperialMeasurement {
     #[serde(alias = "Imperial")]
-    imperial: Temperature,
+    imperial: Measurement,
 }
 
 #[derive(Deserialize, Debug)]
@@ -55,24 +64,128 @@ struct WeatherResponse {
     weather: String,
 
     #[serde(alias = "Temperature")]
-    temp: Temperature,
+    temp: Measurement,
 
     #[serde(default, alias = "RealFeelTemperature")]
-    feels_like_temp: Option<Temperature>,
+    feels_like_temp: Option<Measurement>,
+
+    /// Percent chance, from 0 to 100.
+    #[serde(default, alias = "PrecipitationProbability")]
+    precip_probability: Option<f64>,
+
+    #[serde(default, alias = "Wind")]
+    wind: Option<Wind<Measurement>>,
+
+    #[serde(default, alias = "WindGust")]
+    wind_gust: Option<Wind<Measurement>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DailyResponse {
+    #[serde(alias = "DailyForecasts")]
+    daily_forecasts: Vec<DailyForecast>,
 }
 
 #[derive(Deserialize, Debug)]
-struct Temperature {
+struct DailyForecast {
+    #[serde(alias = "EpochDate", with = "ts_seconds")]
+    date: DateTime<Utc>,
+
+    #[serde(alias = "Temperature")]
+    temp: DailyTemperature,
+
+    #[serde(alias = "Day")]
+    day: DayPart,
+}
+
+#[derive(Deserialize, Debug)]
+struct DailyTemperature {
+    #[serde(alias = "Minimum")]
+    min: Measurement,
+
+    #[serde(alias = "Maximum")]
+    max: Measurement,
+}
+
+#[derive(Deserialize, Debug)]
+struct DayPart {
+    #[serde(alias = "IconPhrase")]
+    weather: String,
+}
+
+/// Current conditions report speeds in both unit systems, while hourly
+/// forecasts only report the requested one, hence the speed type parameter.
+#[derive(Deserialize, Debug)]
+struct Wind<S> {
+    #[serde(alias = "Speed")]
+    speed: S,
+
+    #[serde(default, alias = "Direction")]
+    direction: Option<WindDirection>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WindDirection {
+    #[serde(alias = "Degrees")]
+    degrees: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct Measurement {
     #[serde(alias = "Value")]
     value: f64,
 }
 
+#[derive(Deserialize, Debug)]
+struct AlertResponse {
+    #[serde(alias = "Description")]
+    description: LocalizedText,
+
+    #[serde(alias = "Source")]
+    source: String,
+
+    #[serde(default, alias = "Area")]
+    areas: Vec<AlertArea>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LocalizedText {
+    #[serde(alias = "Localized")]
+    localized: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AlertArea {
+    #[serde(alias = "EpochStartTime", with = "ts_seconds")]
+    start: DateTime<Utc>,
+
+    #[serde(alias = "EpochEndTime", with = "ts_seconds")]
+    end: DateTime<Utc>,
+
+    #[serde(default, alias = "Summary")]
+    summary: String,
+
+    #[serde(default, alias = "Text")]
+    text: Option<String>,
+}
+
 fn current_to_weather(curr: CurrentConditionsResponse, timezone: Tz) -> Weather {
     Weather {
         timestamp: curr.timestamp.with_timezone(&timezone),
         summary: normalize_weather(&curr.weather),
+        units: Units::Imperial,
         temp: curr.temp.imperial.value,
         apparent_temp: curr.feels_like_temp.map(|t| t.imperial.value),
+        precip_probability: None,
+        precip_amount: None,
+        wind_speed: curr.wind.as_ref().map(|w| w.speed.imperial.value),
+        wind_gust: curr.wind_gust.as_ref().map(|w| w.speed.imperial.value),
+        wind_bearing: curr
+            .wind
+            .as_ref()
+            .and_then(|w| w.direction.as_ref())
+            .map(|d| d.degrees),
+        spread: None,
     }
 }
 
@@ -80,11 +193,49 @@ fn forecast_to_weather(weather: WeatherResponse, timezone: Tz) -> Weather {
     Weather {
         timestamp: weather.timestamp.with_timezone(&timezone),
         summary: normalize_weather(&weather.weather),
+        units: Units::Imperial,
         temp: weather.temp.value,
         apparent_temp: weather.feels_like_temp.map(|f| f.value),
+        precip_probability: weather.precip_probability.map(|p| p / 100.0),
+        precip_amount: None,
+        wind_speed: weather.wind.as_ref().map(|w| w.speed.value),
+        wind_gust: weather.wind_gust.as_ref().map(|w| w.speed.value),
+        wind_bearing: weather
+            .wind
+            .as_ref()
+            .and_then(|w| w.direction.as_ref())
+            .map(|d| d.degrees),
+        spread: None,
     }
 }
 
+fn daily_to_daily_weather(daily: DailyForecast, timezone: Tz) -> DailyWeather {
+    DailyWeather {
+        date: daily.date.with_timezone(&timezone).date_naive(),
+        summary: normalize_weather(&daily.day.weather),
+        units: Units::Imperial,
+        high: daily.temp.max.value,
+        low: daily.temp.min.value,
+    }
+}
+
+/// An alert may list several areas; the first one's time window and text are
+/// used. Alerts without any area have no time bounds and are dropped.
+fn alert_to_weather_alert(alert: AlertResponse, timezone: Tz) -> Option<WeatherAlert> {
+    let area = alert.areas.into_iter().next()?;
+
+    Some(WeatherAlert {
+        event: alert.description.localized,
+        sender_name: alert.source,
+        start: area.start.with_timezone(&timezone),
+        end: area.end.with_timezone(&timezone),
+        description: area
+            .text
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or(area.summary),
+    })
+}
+
 fn normalize_weather(weather: &str) -> String {
     weather
         .replace("w/", "with")
@@ -108,6 +259,22 @@ async fn query_current_conditions(api_key: &ApiKey, location_id: &str) -> Result
     .await
 }
 
+async fn query_alerts(api_key: &ApiKey, location_id: &str) -> Result<String> {
+    query::http_get(
+        &format!("https://dataservice.accuweather.com/alerts/v1/{location_id}"),
+        &[("apikey", api_key.as_str()), ("details", "true")],
+    )
+    .await
+}
+
+async fn query_daily(api_key: &ApiKey, location_id: &str) -> Result<String> {
+    query::http_get(
+        &format!("https://dataservice.accuweather.com/forecasts/v1/daily/5day/{location_id}"),
+        &[("apikey", api_key.as_str())],
+    )
+    .await
+}
+
 async fn query_weather(api_key: &ApiKey, location_id: &str) -> Result<String> {
     query::http_get(
         &format!("https://dataservice.accuweather.com/forecasts/v1/hourly/12hour/{location_id}"),
@@ -128,6 +295,8 @@ pub async fn get_weather(
     let weather_cache_path = dated_cache_path(&format!("accuweather-weather_{token_suffix}"));
     let current_conditions_cache_path =
         dated_cache_path(&format!("accuweather-curr_{token_suffix}"));
+    let alerts_cache_path = dated_cache_path(&format!("accuweather-alerts_{token_suffix}"));
+    let daily_cache_path = dated_cache_path(&format!("accuweather-daily_{token_suffix}"));
 
     let location: LocationResponse =
         try_cached_query_json(cache_mode, &location_cache_path, || {
@@ -167,19 +336,76 @@ pub async fn get_weather(
         )
     })?;
 
+    let daily_data = try_cached_query(cache_mode, &daily_cache_path, || {
+        query_daily(api_key, &location.id)
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get daily forecast for location ID {}",
+            location.id
+        )
+    })?;
+
+    let alerts_data = try_cached_query(cache_mode, &alerts_cache_path, || {
+        query_alerts(api_key, &location.id)
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get weather alerts for location ID {}",
+            location.id
+        )
+    });
+
     let current = parse_current_conditions(&current_conditions, timezone)
         .with_context(|| "Failed to parse current weather conditions")?;
     let upcoming = parse_weather(&weather_data, timezone)
         .with_context(|| "Failed to parse weather forecast data")?;
+    let daily = parse_daily(&daily_data, timezone)
+        .with_context(|| "Failed to parse daily forecast data")?;
+    let alerts = alerts_or_empty(alerts_data.and_then(|data| {
+        parse_alerts(&data, timezone).with_context(|| "Failed to parse weather alerts")
+    }));
 
     Ok(WeatherForecast {
         timezone,
         current,
         upcoming,
-        alerts: Vec::new(), // AccuWeather alerts are not currently implemented
+        daily,
+        alerts: filter_alerts(alerts, timezone),
     })
 }
 
+pub struct AccuWeather;
+
+impl ForecastSource for AccuWeather {
+    fn id(&self) -> &'static str {
+        "accuweather"
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        location: &'a Location,
+        options: &'a FetchOptions,
+    ) -> ForecastFuture<'a> {
+        Box::pin(async move {
+            let api_key = options.require_api_key(self.id())?;
+            get_weather(
+                options.cache_mode,
+                api_key,
+                location.latitude,
+                location.longitude,
+            )
+            .await
+        })
+    }
+}
+
 fn parse_current_conditions(response: &str, timezone: Tz) -> Result<Weather> {
     let response: Vec<CurrentConditionsResponse> = serde_json::from_str(response)
         .with_context(|| "Failed to deserialize current conditions JSON from AccuWeather")?;
@@ -200,6 +426,27 @@ fn parse_weather(response: &str, timezone: Tz) -> Result<Vec<Weather>> {
         .collect())
 }
 
+fn parse_daily(response: &str, timezone: Tz) -> Result<Vec<DailyWeather>> {
+    let response: DailyResponse = serde_json::from_str(response)
+        .with_context(|| "Failed to deserialize daily forecast JSON from AccuWeather")?;
+
+    Ok(response
+        .daily_forecasts
+        .into_iter()
+        .map(|d| daily_to_daily_weather(d, timezone))
+        .collect())
+}
+
+fn parse_alerts(response: &str, timezone: Tz) -> Result<Vec<WeatherAlert>> {
+    let response: Vec<AlertResponse> = serde_json::from_str(response)
+        .with_context(|| "Failed to deserialize alerts JSON from AccuWeather")?;
+
+    Ok(response
+        .into_iter()
+        .filter_map(|a| alert_to_weather_alert(a, timezone))
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -210,6 +457,10 @@ mod test {
 
     const WEATHER_RESPONSE: &str = r#"[{"DateTime":"2023-03-19T09:00:00-04:00","EpochDateTime":1679230800,"WeatherIcon":1,"IconPhrase":"Sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":32,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":25,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":23,"Unit":"F","UnitType":18,"Phrase":"Very Cold"},"WetBulbTemperature":{"Value":24,"Unit":"F","UnitType":18},"DewPoint":{"Value":3,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":9,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":271,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":21,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":271,"Localized":"W","English":"W"}},"RelativeHumidity":29,"IndoorRelativeHumidity":18,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":33000,"Unit":"ft","UnitType":0},"UVIndex":1,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":5,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":394.39,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=9&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=9&lang=en-us"},{"DateTime":"2023-03-19T10:00:00-04:00","EpochDateTime":1679234400,"WeatherIcon":2,"IconPhrase":"Mostly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":33,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":29,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":25,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":25,"Unit":"F","UnitType":18},"DewPoint":{"Value":4,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":9,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":272,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":21,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":272,"Localized":"W","English":"W"}},"RelativeHumidity":29,"IndoorRelativeHumidity":19,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":2,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":10,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":523.57,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=10&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=10&lang=en-us"},{"DateTime":"2023-03-19T11:00:00-04:00","EpochDateTime":1679238000,"WeatherIcon":2,"IconPhrase":"Mostly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":35,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":30,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":24,"Unit":"F","UnitType":18,"Phrase":"Very Cold"},"WetBulbTemperature":{"Value":27,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":12,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":275,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":23,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":275,"Localized":"W","English":"W"}},"RelativeHumidity":30,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":4,"UVIndexText":"Moderate","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":10,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":627.43,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=11&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=11&lang=en-us"},{"DateTime":"2023-03-19T12:00:00-04:00","EpochDateTime":1679241600,"WeatherIcon":2,"IconPhrase":"Mostly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":37,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":32,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":26,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":28,"Unit":"F","UnitType":18},"DewPoint":{"Value":6,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":13,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":277,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":27,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":277,"Localized":"W","English":"W"}},"RelativeHumidity":28,"IndoorRelativeHumidity":20,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":4,"UVIndexText":"Moderate","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":10,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":691.88,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=12&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=12&lang=en-us"},{"DateTime":"2023-03-19T13:00:00-04:00","EpochDateTime":1679245200,"WeatherIcon":2,"IconPhrase":"Mostly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":39,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":33,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":27,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":29,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":15,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":276,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":29,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":276,"Localized":"W","English":"W"}},"RelativeHumidity":26,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":5,"UVIndexText":"Moderate","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":10,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":716.92,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=13&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=13&lang=en-us"},{"DateTime":"2023-03-19T14:00:00-04:00","EpochDateTime":1679248800,"WeatherIcon":4,"IconPhrase":"Intermittent clouds","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":40,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":33,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":28,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":15,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":279,"Localized":"W","English":"W"}},"WindGust":{"Speed":{"Value":29,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":279,"Localized":"W","English":"W"}},"RelativeHumidity":25,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":4,"UVIndexText":"Moderate","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":59,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":447.42,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=14&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=14&lang=en-us"},{"DateTime":"2023-03-19T15:00:00-04:00","EpochDateTime":1679252400,"WeatherIcon":4,"IconPhrase":"Intermittent clouds","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":40,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":33,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":29,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":16,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":284,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":29,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":284,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":25,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":3,"UVIndexText":"Moderate","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":66,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":379.51,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=15&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=15&lang=en-us"},{"DateTime":"2023-03-19T16:00:00-04:00","EpochDateTime":1679256000,"WeatherIcon":4,"IconPhrase":"Intermittent clouds","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":42,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":33,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":31,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":31,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":16,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":286,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":29,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":286,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":23,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":2,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":67,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":320.88,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=16&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=16&lang=en-us"},{"DateTime":"2023-03-19T17:00:00-04:00","EpochDateTime":1679259600,"WeatherIcon":4,"IconPhrase":"Intermittent clouds","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":41,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":31,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":29,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":16,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":288,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":29,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":288,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":24,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":1,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":58,"Evapotranspiration":{"Value":0.01,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":272.51,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=17&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=17&lang=en-us"},{"DateTime":"2023-03-19T18:00:00-04:00","EpochDateTime":1679263200,"WeatherIcon":3,"IconPhrase":"Partly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":40,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":30,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":30,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":7,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":14,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":291,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":25,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":291,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":25,"IndoorRelativeHumidity":21,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":0,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":46,"Evapotranspiration":{"Value":0,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":184.01,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=18&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=18&lang=en-us"},{"DateTime":"2023-03-19T19:00:00-04:00","EpochDateTime":1679266800,"WeatherIcon":3,"IconPhrase":"Partly sunny","HasPrecipitation":false,"IsDaylight":true,"Temperature":{"Value":39,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":30,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":30,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":8,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":12,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":295,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":22,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":295,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":28,"IndoorRelativeHumidity":22,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":0,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":46,"Evapotranspiration":{"Value":0,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":29,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=19&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=19&lang=en-us"},{"DateTime":"2023-03-19T20:00:00-04:00","EpochDateTime":1679270400,"WeatherIcon":33,"IconPhrase":"Clear","HasPrecipitation":false,"IsDaylight":false,"Temperature":{"Value":39,"Unit":"F","UnitType":18},"RealFeelTemperature":{"Value":31,"Unit":"F","UnitType":18,"Phrase":"Cold"},"RealFeelTemperatureShade":{"Value":31,"Unit":"F","UnitType":18,"Phrase":"Cold"},"WetBulbTemperature":{"Value":30,"Unit":"F","UnitType":18},"DewPoint":{"Value":10,"Unit":"F","UnitType":18},"Wind":{"Speed":{"Value":10,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":300,"Localized":"WNW","English":"WNW"}},"WindGust":{"Speed":{"Value":18,"Unit":"mi/h","UnitType":9},"Direction":{"Degrees":300,"Localized":"WNW","English":"WNW"}},"RelativeHumidity":30,"IndoorRelativeHumidity":24,"Visibility":{"Value":10,"Unit":"mi","UnitType":2},"Ceiling":{"Value":30000,"Unit":"ft","UnitType":0},"UVIndex":0,"UVIndexText":"Low","PrecipitationProbability":0,"ThunderstormProbability":0,"RainProbability":0,"SnowProbability":0,"IceProbability":0,"TotalLiquid":{"Value":0,"Unit":"in","UnitType":1},"Rain":{"Value":0,"Unit":"in","UnitType":1},"Snow":{"Value":0,"Unit":"in","UnitType":1},"Ice":{"Value":0,"Unit":"in","UnitType":1},"CloudCover":0,"Evapotranspiration":{"Value":0,"Unit":"in","UnitType":1},"SolarIrradiance":{"Value":0,"Unit":"W/m²","UnitType":33},"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=20&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/hourly-weather-forecast/2627484?day=1&hbhhour=20&lang=en-us"}]"#;
 
+    const DAILY_RESPONSE: &str = r#"{"Headline":{"EffectiveDate":"2023-03-20T07:00:00-04:00","EffectiveEpochDate":1679310000,"Severity":4,"Text":"Noticeably warmer tomorrow","Category":"warmer","EndDate":null,"EndEpochDate":null,"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?lang=en-us"},"DailyForecasts":[{"Date":"2023-03-19T07:00:00-04:00","EpochDate":1679223600,"Temperature":{"Minimum":{"Value":27.0,"Unit":"F","UnitType":18},"Maximum":{"Value":42.0,"Unit":"F","UnitType":18}},"Day":{"Icon":1,"IconPhrase":"Sunny","HasPrecipitation":false},"Night":{"Icon":33,"IconPhrase":"Clear","HasPrecipitation":false},"Sources":["AccuWeather"],"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?day=1&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?day=1&lang=en-us"},{"Date":"2023-03-20T07:00:00-04:00","EpochDate":1679310000,"Temperature":{"Minimum":{"Value":35.0,"Unit":"F","UnitType":18},"Maximum":{"Value":54.0,"Unit":"F","UnitType":18}},"Day":{"Icon":4,"IconPhrase":"Intermittent clouds","HasPrecipitation":false},"Night":{"Icon":38,"IconPhrase":"Mostly cloudy","HasPrecipitation":false},"Sources":["AccuWeather"],"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?day=2&lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/daily-weather-forecast/2627484?day=2&lang=en-us"}]}"#;
+
+    const ALERTS_RESPONSE: &str = r##"[{"CountryCode":"US","AlertID":31071935,"Description":{"Localized":"Winter Storm Warning","English":"Winter Storm Warning"},"Category":"WINTER STORM","Priority":31,"Type":"WSW","TypeID":"WSW","Class":null,"Level":"Warning","Color":{"Name":"Pink","Red":255,"Green":105,"Blue":180,"Hex":"#FF69B4"},"Source":"U.S. National Weather Service","SourceId":2,"Disclaimer":null,"Area":[{"Name":"New York (Manhattan)","StartTime":"2023-03-19T19:00:00-04:00","EpochStartTime":1679266800,"EndTime":"2023-03-20T10:00:00-04:00","EpochEndTime":1679320800,"LastAction":{"Localized":"New","English":"New"},"Text":"...WINTER STORM WARNING IN EFFECT FROM 7 PM THIS EVENING TO 10 AM EDT MONDAY...\n\n* WHAT...Heavy snow expected. Total snow accumulations of 6 to 10 inches.","LanguageCode":"en-us","Summary":"Winter Storm Warning in effect until 10:00 AM EDT. Source: U.S. National Weather Service"}],"HaveReadyStatements":false,"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/weather-warnings/2627484?lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/weather-warnings/2627484?lang=en-us"},{"CountryCode":"US","AlertID":31071936,"Description":{"Localized":"Special Weather Statement","English":"Special Weather Statement"},"Category":"SPECIAL WEATHER STATEMENT","Priority":80,"Type":"SPS","TypeID":"SPS","Class":null,"Level":"Statement","Color":{"Name":"Moccasin","Red":255,"Green":228,"Blue":181,"Hex":"#FFE4B5"},"Source":"U.S. National Weather Service","SourceId":2,"Disclaimer":null,"Area":[{"Name":"New York (Manhattan)","StartTime":"2023-03-19T09:00:00-04:00","EpochStartTime":1679230800,"EndTime":"2023-03-19T12:00:00-04:00","EpochEndTime":1679241600,"LastAction":{"Localized":"New","English":"New"},"Text":"","LanguageCode":"en-us","Summary":"Areas of fog until noon. Source: U.S. National Weather Service"}],"HaveReadyStatements":false,"MobileLink":"http://www.accuweather.com/en/us/midtown-ny/10022/weather-warnings/2627484?lang=en-us","Link":"http://www.accuweather.com/en/us/midtown-ny/10022/weather-warnings/2627484?lang=en-us"},{"CountryCode":"US","AlertID":31071937,"Description":{"Localized":"Flood Watch","English":"Flood Watch"},"Category":"FLOOD","Priority":40,"Type":"FFA","TypeID":"FFA","Class":null,"Level":"Watch","Color":{"Name":"SeaGreen","Red":46,"Green":139,"Blue":87,"Hex":"#2E8B57"},"Source":"U.S. National Weather Service","SourceId":2,"Disclaimer":null,"Area":[],"HaveReadyStatements":false,"MobileLink":"","Link":""}]"##;
+
     #[test]
     pub fn test_deserialize_location_response() -> Result<()> {
         let location_response: LocationResponse = serde_json::from_str(LOCATION_RESPONSE)?;
@@ -255,7 +506,73 @@ mod test {
             25.0,
             location_response[0].feels_like_temp.as_ref().unwrap().value
         );
+        assert_eq!(Some(0.0), location_response[0].precip_probability);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_daily_response() -> Result<()> {
+        let daily = parse_daily(DAILY_RESPONSE, Tz::America__New_York)?;
+
+        assert_eq!(2, daily.len());
+        assert_eq!("2023-03-19", daily[0].date.to_string());
+        assert_eq!("Sunny", daily[0].summary);
+        assert_eq!(42.0, daily[0].high);
+        assert_eq!(27.0, daily[0].low);
+        assert_eq!("Intermittent clouds", daily[1].summary);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_wind() -> Result<()> {
+        let current = parse_current_conditions(CURRENT_CONDITIONS_RESPONSE, Tz::UTC)?;
+        assert_eq!(Some(5.8), current.wind_speed);
+        assert_eq!(Some(21.9), current.wind_gust);
+        assert_eq!(Some(0.0), current.wind_bearing);
+
+        let weather = parse_weather(WEATHER_RESPONSE, Tz::UTC)?;
+        assert_eq!(Some(9.0), weather[0].wind_speed);
+        assert_eq!(Some(21.0), weather[0].wind_gust);
+        assert_eq!(Some(271.0), weather[0].wind_bearing);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_alerts_response() -> Result<()> {
+        let alerts = parse_alerts(ALERTS_RESPONSE, Tz::America__New_York)?;
+
+        // The Flood Watch has no area, so it has no time bounds and is dropped.
+        assert_eq!(2, alerts.len());
+
+        assert_eq!("Winter Storm Warning", alerts[0].event);
+        assert_eq!("U.S. National Weather Service", alerts[0].sender_name);
+        assert_eq!(1679266800, alerts[0].start.timestamp());
+        assert_eq!(1679320800, alerts[0].end.timestamp());
+        assert!(alerts[0].description.contains("Heavy snow expected"));
+
+        // Empty alert text falls back to the area summary.
+        assert_eq!("Special Weather Statement", alerts[1].event);
+        assert!(alerts[1].description.starts_with("Areas of fog until noon"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_empty_alerts_response() -> Result<()> {
+        assert!(parse_alerts("[]", Tz::America__New_York)?.is_empty());
 
         Ok(())
     }
+
+    #[test]
+    pub fn test_failed_alerts_leave_no_alerts() {
+        let tz = Tz::America__New_York;
+
+        assert_eq!(2, alerts_or_empty(parse_alerts(ALERTS_RESPONSE, tz)).len());
+        assert!(alerts_or_empty(Err(anyhow!("401 Unauthorized"))).is_empty());
+        assert!(alerts_or_empty(parse_alerts(r#"{"Code":"Unauthorized"}"#, tz)).is_empty());
+    }
 }
@@ -0,0 +1,435 @@
+//! Blend forecasts from several providers into a single forecast, hour by
+//! hour, recording how much the providers disagreed.
+
+use crate::weather::{DailyWeather, Spread, Weather, WeatherAlert, WeatherForecast};
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+/// Merges forecasts, given in provider chain order, into one. Returns `None`
+/// if there are no forecasts to merge.
+///
+/// The current conditions are blended across all providers; upcoming hours
+/// are aligned by the hour they fall in and blended wherever at least one
+/// provider has data, as are days of the daily outlook. The timezone is taken
+/// from the first forecast.
+pub(crate) fn merge(forecasts: Vec<(&'static str, WeatherForecast)>) -> Option<WeatherForecast> {
+    let timezone = forecasts.first()?.1.timezone;
+
+    let mut currents = Vec::with_capacity(forecasts.len());
+    let mut hours: BTreeMap<i64, Vec<(&'static str, Weather)>> = BTreeMap::new();
+    let mut days: BTreeMap<NaiveDate, Vec<DailyWeather>> = BTreeMap::new();
+    let mut alerts: Vec<WeatherAlert> = Vec::new();
+
+    for (id, forecast) in forecasts {
+        currents.push((id, forecast.current));
+
+        for weather in forecast.upcoming {
+            hours
+                .entry(hour_key(&weather.timestamp))
+                .or_default()
+                .push((id, weather));
+        }
+
+        for daily in forecast.daily {
+            days.entry(daily.date).or_default().push(daily);
+        }
+
+        for alert in forecast.alerts {
+            if !alerts.iter().any(|a| is_same_alert(a, &alert)) {
+                alerts.push(alert);
+            }
+        }
+    }
+
+    Some(WeatherForecast {
+        current: blend(currents, timezone),
+        upcoming: hours
+            .into_values()
+            .map(|samples| blend(samples, timezone))
+            .collect(),
+        daily: days.into_values().map(blend_daily).collect(),
+        timezone,
+        alerts,
+    })
+}
+
+fn hour_key(timestamp: &DateTime<Tz>) -> i64 {
+    timestamp.timestamp().div_euclid(3600)
+}
+
+/// Providers word and time the same alert slightly differently, so treat
+/// alerts for the same event with overlapping windows as one.
+pub(super) fn is_same_alert(a: &WeatherAlert, b: &WeatherAlert) -> bool {
+    a.event.eq_ignore_ascii_case(&b.event) && a.start <= b.end && b.start <= a.end
+}
+
+/// Blends one hour's samples, which must not be empty.
+fn blend(samples: Vec<(&'static str, Weather)>, timezone: Tz) -> Weather {
+    let temps: Vec<f64> = samples.iter().map(|(_, w)| w.temp).collect();
+    let apparent_temps: Vec<f64> = samples
+        .iter()
+        .filter_map(|(_, w)| w.apparent_temp)
+        .collect();
+    let precip_probabilities: Vec<f64> = samples
+        .iter()
+        .filter_map(|(_, w)| w.precip_probability)
+        .collect();
+    let precip_amounts: Vec<f64> = samples
+        .iter()
+        .filter_map(|(_, w)| w.precip_amount)
+        .collect();
+    let wind_speeds: Vec<f64> = samples.iter().filter_map(|(_, w)| w.wind_speed).collect();
+    let wind_gusts: Vec<f64> = samples.iter().filter_map(|(_, w)| w.wind_gust).collect();
+    let (summary, votes) = majority_summary(samples.iter().map(|(_, w)| w.summary.as_str()));
+
+    let spread = Spread {
+        providers: samples.iter().map(|(id, _)| id.to_string()).collect(),
+        temp_min: temps.iter().copied().fold(f64::INFINITY, f64::min),
+        temp_max: temps.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        summary_agreement: votes as f64 / samples.len() as f64,
+    };
+
+    Weather {
+        timestamp: samples[0].1.timestamp.with_timezone(&timezone),
+        summary,
+        units: samples[0].1.units,
+        temp: median(temps),
+        apparent_temp: median_of_any(apparent_temps),
+        precip_probability: median_of_any(precip_probabilities),
+        precip_amount: median_of_any(precip_amounts),
+        wind_speed: median_of_any(wind_speeds),
+        wind_gust: median_of_any(wind_gusts),
+        // Bearings can't be averaged naively (350° and 10° are close), so
+        // take the earliest provider's.
+        wind_bearing: samples.iter().find_map(|(_, w)| w.wind_bearing),
+        spread: Some(spread),
+    }
+}
+
+/// Blends one day's outlooks, which must not be empty.
+fn blend_daily(samples: Vec<DailyWeather>) -> DailyWeather {
+    let (summary, _) = majority_summary(samples.iter().map(|d| d.summary.as_str()));
+
+    DailyWeather {
+        date: samples[0].date,
+        summary,
+        units: samples[0].units,
+        high: median(samples.iter().map(|d| d.high).collect()),
+        low: median(samples.iter().map(|d| d.low).collect()),
+    }
+}
+
+/// The median, or `None` if no provider reported a value.
+fn median_of_any(values: Vec<f64>) -> Option<f64> {
+    (!values.is_empty()).then(|| median(values))
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Words that place a summary in each [`Condition`], checked in order so
+/// e.g. "Rain And Snow" counts as snow and "Thunderstorms And Rain" as a
+/// storm.
+const CONDITION_WORDS: [(Condition, &[&str]); 5] = [
+    (Condition::Storm, &["thunder", "storm"]),
+    (
+        Condition::Snow,
+        &["snow", "sleet", "flurr", "ice", "freezing"],
+    ),
+    (Condition::Rain, &["rain", "shower", "drizzle"]),
+    (Condition::Cloudy, &["cloud", "overcast"]),
+    (Condition::Clear, &["clear", "sun", "fair"]),
+];
+
+/// Conditions shared by every provider's wording, e.g. NWS "Mostly Sunny" and
+/// OpenWeather "Clear", so their summaries can be compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    Clear,
+    Cloudy,
+    Rain,
+    Snow,
+    Storm,
+    /// A summary that fits none of the above, e.g. "Fog", lowercased.
+    Other(String),
+}
+
+impl Condition {
+    fn of(summary: &str) -> Self {
+        let summary = summary.to_lowercase();
+
+        CONDITION_WORDS
+            .iter()
+            .find(|(_, words)| words.iter().any(|word| summary.contains(word)))
+            .map_or(Condition::Other(summary), |(condition, _)| {
+                condition.clone()
+            })
+    }
+}
+
+/// The summary for the most common [`Condition`] and how many providers gave
+/// it, worded as the earliest provider in the chain with that condition
+/// worded it. Ties go to whichever condition appears first.
+fn majority_summary<'a>(summaries: impl IntoIterator<Item = &'a str>) -> (String, usize) {
+    let mut tallies: Vec<(Condition, &str, usize)> = Vec::new();
+
+    for summary in summaries {
+        let condition = Condition::of(summary);
+        match tallies
+            .iter_mut()
+            .find(|(tallied, _, _)| *tallied == condition)
+        {
+            Some((_, _, count)) => *count += 1,
+            None => tallies.push((condition, summary, 1)),
+        }
+    }
+
+    let mut winner = &tallies[0];
+    for tally in &tallies[1..] {
+        if tally.2 > winner.2 {
+            winner = tally;
+        }
+    }
+
+    (winner.1.to_string(), winner.2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::weather::Units;
+    use chrono::{Duration, TimeZone};
+
+    fn at_hour(hour: u32) -> DateTime<Tz> {
+        Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, 15, hour, 0, 0)
+            .unwrap()
+    }
+
+    fn weather(hour: u32, summary: &str, temp: f64) -> Weather {
+        Weather {
+            timestamp: at_hour(hour),
+            summary: summary.to_string(),
+            units: Units::Imperial,
+            temp,
+            apparent_temp: None,
+            precip_probability: None,
+            precip_amount: None,
+            wind_speed: None,
+            wind_gust: None,
+            wind_bearing: None,
+            spread: None,
+        }
+    }
+
+    fn forecast(current: Weather, upcoming: Vec<Weather>) -> WeatherForecast {
+        WeatherForecast {
+            current,
+            upcoming,
+            daily: Vec::new(),
+            timezone: Tz::America__New_York,
+            alerts: Vec::new(),
+        }
+    }
+
+    fn alert(event: &str, start_hour: u32, end_hour: u32) -> WeatherAlert {
+        WeatherAlert {
+            event: event.to_string(),
+            sender_name: "NWS".to_string(),
+            start: at_hour(start_hour),
+            end: at_hour(end_hour),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn median_of_odd_and_even_counts() {
+        assert_eq!(50.0, median(vec![70.0, 50.0, 40.0]));
+        assert_eq!(55.0, median(vec![70.0, 50.0, 40.0, 60.0]));
+        assert_eq!(42.0, median(vec![42.0]));
+    }
+
+    #[test]
+    fn majority_summary_prefers_most_votes() {
+        assert_eq!(
+            ("Rain".to_string(), 2),
+            majority_summary(["Cloudy", "Rain", "rain"])
+        );
+    }
+
+    #[test]
+    fn majority_summary_compares_conditions_not_wording() {
+        assert_eq!(
+            ("Mostly Sunny".to_string(), 2),
+            majority_summary(["Mostly Sunny", "Rain", "Clear"])
+        );
+        assert_eq!(
+            ("Light Rain".to_string(), 2),
+            majority_summary(["Cloudy", "Light Rain", "Chance Showers"])
+        );
+        assert_eq!(
+            ("Fog".to_string(), 2),
+            majority_summary(["Fog", "Overcast", "fog"])
+        );
+    }
+
+    #[test]
+    fn condition_of_summary() {
+        assert_eq!(Condition::Clear, Condition::of("Mostly Sunny"));
+        assert_eq!(Condition::Cloudy, Condition::of("Partly Cloudy"));
+        assert_eq!(Condition::Rain, Condition::of("Light Drizzle"));
+        assert_eq!(
+            Condition::Snow,
+            Condition::of("Chance Rain And Snow Showers")
+        );
+        assert_eq!(Condition::Storm, Condition::of("Thunderstorms And Rain"));
+        assert_eq!(Condition::Other("haze".to_string()), Condition::of("Haze"));
+    }
+
+    #[test]
+    fn majority_summary_tie_goes_to_earliest_provider() {
+        assert_eq!(
+            ("Cloudy".to_string(), 1),
+            majority_summary(["Cloudy", "Rain"])
+        );
+    }
+
+    #[test]
+    fn merge_empty_is_none() {
+        assert!(merge(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn merge_blends_hour_by_hour() {
+        let merged = merge(vec![
+            (
+                "a",
+                forecast(
+                    weather(7, "Cloudy", 40.0),
+                    vec![weather(8, "Cloudy", 41.0), weather(12, "Rain", 50.0)],
+                ),
+            ),
+            (
+                "b",
+                forecast(
+                    weather(7, "Cloudy", 42.0),
+                    vec![weather(8, "Rain", 45.0), weather(12, "Rain", 54.0)],
+                ),
+            ),
+            (
+                "c",
+                forecast(
+                    weather(7, "Sunny", 44.0),
+                    vec![
+                        weather(8, "Rain", 43.0),
+                        weather(12, "Cloudy", 52.0),
+                        weather(18, "Clear", 47.0),
+                    ],
+                ),
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!("Cloudy", merged.current.summary);
+        assert_eq!(42.0, merged.current.temp);
+
+        assert_eq!(3, merged.upcoming.len());
+
+        let eight = &merged.upcoming[0];
+        assert_eq!(at_hour(8), eight.timestamp);
+        assert_eq!("Rain", eight.summary);
+        assert_eq!(43.0, eight.temp);
+
+        let spread = eight.spread.as_ref().unwrap();
+        assert_eq!(vec!["a", "b", "c"], spread.providers);
+        assert_eq!(41.0, spread.temp_min);
+        assert_eq!(45.0, spread.temp_max);
+        assert!((spread.summary_agreement - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        // Only one provider reached 6pm, so its data is used as-is.
+        let six = &merged.upcoming[2];
+        assert_eq!("Clear", six.summary);
+        assert_eq!(vec!["c"], six.spread.as_ref().unwrap().providers);
+    }
+
+    #[test]
+    fn merge_aligns_samples_within_the_same_hour() {
+        let mut late = weather(8, "Cloudy", 44.0);
+        late.timestamp += Duration::minutes(30);
+
+        let merged = merge(vec![
+            (
+                "a",
+                forecast(weather(7, "Cloudy", 40.0), vec![weather(8, "Cloudy", 40.0)]),
+            ),
+            ("b", forecast(weather(7, "Cloudy", 40.0), vec![late])),
+        ])
+        .unwrap();
+
+        assert_eq!(1, merged.upcoming.len());
+        assert_eq!(42.0, merged.upcoming[0].temp);
+    }
+
+    #[test]
+    fn merge_blends_daily_outlook_by_date() {
+        let day = |date: u32, summary: &str, high: f64, low: f64| DailyWeather {
+            date: NaiveDate::from_ymd_opt(2024, 1, date).unwrap(),
+            summary: summary.to_string(),
+            units: Units::Imperial,
+            high,
+            low,
+        };
+
+        let mut a = forecast(weather(7, "Cloudy", 40.0), Vec::new());
+        a.daily = vec![day(15, "Rain", 50.0, 38.0), day(16, "Clear", 45.0, 30.0)];
+
+        let mut b = forecast(weather(7, "Cloudy", 40.0), Vec::new());
+        b.daily = vec![day(15, "rain", 54.0, 40.0)];
+
+        let merged = merge(vec![("a", a), ("b", b)]).unwrap();
+
+        assert_eq!(2, merged.daily.len());
+        assert_eq!("Rain", merged.daily[0].summary);
+        assert_eq!(52.0, merged.daily[0].high);
+        assert_eq!(39.0, merged.daily[0].low);
+        assert_eq!("Clear", merged.daily[1].summary);
+    }
+
+    #[test]
+    fn merge_deduplicates_alerts_by_event_and_window() {
+        let mut a = forecast(weather(7, "Cloudy", 40.0), Vec::new());
+        a.alerts = vec![alert("Flood Watch", 8, 18)];
+
+        let mut b = forecast(weather(7, "Cloudy", 40.0), Vec::new());
+        b.alerts = vec![
+            alert("FLOOD WATCH", 9, 20),
+            alert("Flood Watch", 19, 23),
+            alert("Wind Advisory", 8, 18),
+        ];
+
+        let merged = merge(vec![("a", a), ("b", b)]).unwrap();
+        let events: Vec<_> = merged
+            .alerts
+            .iter()
+            .map(|a| (a.event.as_str(), a.start))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("Flood Watch", at_hour(8)),
+                ("Flood Watch", at_hour(19)),
+                ("Wind Advisory", at_hour(8)),
+            ],
+            events
+        );
+    }
+}
@@ -1,13 +1,26 @@
 use anyhow::{Context, Result, anyhow};
-use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc, Weekday};
 use chrono_tz::Tz;
-use jluszcz_rust_utils::cache::CacheMode;
-use log::{debug, trace};
+use log::{debug, info, trace, warn};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::{Instant, timeout};
 
 pub mod accu_weather;
+mod consensus;
+pub mod nws;
+pub mod open_meteo;
 pub mod open_weather;
+pub mod schema;
+pub mod source;
+
+pub use source::{
+    FetchOptions, ForecastFuture, ForecastSource, Location, register_source, registered_ids,
+};
 
 /// Minimum length for an API key. Soft sanity check to catch obvious
 /// configuration mistakes (e.g. empty or placeholder values), not a security
@@ -54,15 +67,137 @@ impl fmt::Display for ApiKey {
     }
 }
 
-#[derive(Debug)]
+/// Measurement system for temperatures and wind speeds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    /// Degrees Fahrenheit and miles per hour.
+    #[default]
+    Imperial,
+    /// Degrees Celsius and kilometers per hour.
+    Metric,
+}
+
+impl Units {
+    pub const ALL: [Units; 2] = [Units::Imperial, Units::Metric];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Units::Imperial => "imperial",
+            Units::Metric => "metric",
+        }
+    }
+
+    /// Converts a temperature in degrees Fahrenheit to these units.
+    pub fn temp_from_fahrenheit(self, temp: f64) -> f64 {
+        match self {
+            Units::Imperial => temp,
+            Units::Metric => (temp - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    /// Converts a temperature in these units to degrees Fahrenheit.
+    pub fn temp_to_fahrenheit(self, temp: f64) -> f64 {
+        match self {
+            Units::Imperial => temp,
+            Units::Metric => temp * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Converts a speed in miles per hour to these units.
+    pub fn speed_from_mph(self, speed: f64) -> f64 {
+        match self {
+            Units::Imperial => speed,
+            Units::Metric => speed * KM_PER_MILE,
+        }
+    }
+}
+
+const KM_PER_MILE: f64 = 1.609344;
+
+/// A temperature in whole degrees, truncated toward zero, as it's both read
+/// aloud and shown in tables.
+pub fn whole_degrees(temp: f64) -> i64 {
+    temp as i64
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for Units {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|units| units.id().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow!("Unknown units: {s:?} (expected imperial or metric)"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weather {
+    #[serde(with = "schema::timestamp")]
     pub timestamp: DateTime<Tz>,
     pub summary: String,
+    /// Measurement system of `temp`, `apparent_temp` and the wind speeds.
+    pub units: Units,
     pub temp: f64,
     pub apparent_temp: Option<f64>,
+    /// Chance of precipitation during the hour, from 0.0 to 1.0.
+    pub precip_probability: Option<f64>,
+    /// Expected precipitation during the hour, in millimeters.
+    pub precip_amount: Option<f64>,
+    /// Sustained wind speed, in miles or kilometers per hour.
+    pub wind_speed: Option<f64>,
+    /// Peak wind gust, in miles or kilometers per hour.
+    pub wind_gust: Option<f64>,
+    /// Direction the wind blows from, in degrees clockwise from north.
+    pub wind_bearing: Option<f64>,
+    /// Set on consensus forecasts to record how the providers disagreed.
+    pub spread: Option<Spread>,
+}
+
+/// Disagreement between providers for one hour of a consensus forecast.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spread {
+    /// Providers that had data for this hour, in chain order.
+    pub providers: Vec<String>,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    /// Fraction of providers whose summary described the same conditions as
+    /// the chosen one.
+    pub summary_agreement: f64,
 }
 
 impl Weather {
+    /// Converts from imperial, as the sources report it, to `units`.
+    fn into_units(self, units: Units) -> Self {
+        if self.units == units {
+            return self;
+        }
+
+        let temp = |t| units.temp_from_fahrenheit(t);
+        let speed = |s| units.speed_from_mph(s);
+
+        Self {
+            units,
+            temp: temp(self.temp),
+            apparent_temp: self.apparent_temp.map(temp),
+            wind_speed: self.wind_speed.map(speed),
+            wind_gust: self.wind_gust.map(speed),
+            spread: self.spread.map(|spread| Spread {
+                temp_min: temp(spread.temp_min),
+                temp_max: temp(spread.temp_max),
+                ..spread
+            }),
+            ..self
+        }
+    }
+
     #[cfg(test)]
     pub fn test<S>(summary: Option<S>) -> Self
     where
@@ -73,117 +208,669 @@ impl Weather {
             summary: summary
                 .map(|s| s.into())
                 .unwrap_or_else(|| "sunny".to_string()),
+            units: Units::Imperial,
             temp: 72.0,
             apparent_temp: None,
+            precip_probability: None,
+            precip_amount: None,
+            wind_speed: None,
+            wind_gust: None,
+            wind_bearing: None,
+            spread: None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherAlert {
     pub event: String,
     pub sender_name: String,
+    #[serde(with = "schema::timestamp")]
     pub start: DateTime<Tz>,
+    #[serde(with = "schema::timestamp")]
     pub end: DateTime<Tz>,
     pub description: String,
 }
 
-#[derive(Debug)]
+/// Outlook for a whole calendar day in the forecast's timezone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyWeather {
+    pub date: NaiveDate,
+    pub summary: String,
+    /// Measurement system of `high` and `low`.
+    pub units: Units,
+    pub high: f64,
+    pub low: f64,
+}
+
+impl DailyWeather {
+    fn into_units(self, units: Units) -> Self {
+        if self.units == units {
+            return self;
+        }
+
+        Self {
+            units,
+            high: units.temp_from_fahrenheit(self.high),
+            low: units.temp_from_fahrenheit(self.low),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WeatherForecast {
     pub current: Weather,
     pub upcoming: Vec<Weather>,
+    /// Starting with today, for as many days as the source provides.
+    pub daily: Vec<DailyWeather>,
     pub timezone: Tz,
     pub alerts: Vec<WeatherAlert>,
 }
 
-/// A forecast filtered down to the hours worth announcing, plus any active alerts.
-#[derive(Debug)]
+impl WeatherForecast {
+    fn into_units(self, units: Units) -> Self {
+        Self {
+            current: self.current.into_units(units),
+            upcoming: self
+                .upcoming
+                .into_iter()
+                .map(|w| w.into_units(units))
+                .collect(),
+            daily: self
+                .daily
+                .into_iter()
+                .map(|d| d.into_units(units))
+                .collect(),
+            ..self
+        }
+    }
+}
+
+/// A forecast filtered down to the hours worth announcing, plus the daily
+/// outlook and any active alerts.
+///
+/// Serializes with a schema version; see [`schema`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "schema::ReportDocument", try_from = "schema::ReportDocument")]
 pub struct WeatherReport {
     pub weather: Vec<Weather>,
+    /// Every upcoming hour the source provided, before filtering, for
+    /// questions about a specific time.
+    pub hourly: Vec<Weather>,
+    pub daily: Vec<DailyWeather>,
     pub alerts: Vec<WeatherAlert>,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum WeatherProvider {
-    AccuWeather,
-    OpenWeather,
+/// Total time allowed for fetching a forecast across every provider in the
+/// chain. The Lambda timeout is 10s and we still need to summarize alerts and
+/// render the response, so leave headroom rather than spend it all here.
+pub const DEFAULT_FETCH_BUDGET: Duration = Duration::from_secs(7);
+
+/// How a [`WeatherProvider`] with several sources combines them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProviderMode {
+    /// Use the first source that succeeds.
+    #[default]
+    Fallback,
+    /// Query every source concurrently and blend their forecasts.
+    Consensus,
+}
+
+/// Hours of the day to report on, optionally different on weekends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HourSchedule {
+    /// Hours to report on, 0–23; `None` uses 8am, noon and 6pm.
+    pub weekday: Option<Vec<u32>>,
+    /// Hours to report on Saturday and Sunday; `None` uses `weekday`.
+    pub weekend: Option<Vec<u32>>,
+    /// Also report on 10pm on weekends.
+    pub add_weekend_hour: bool,
+}
+
+impl HourSchedule {
+    /// Every hour of interest on the given day of the week.
+    pub fn hours_on(&self, weekday: Weekday) -> Vec<u32> {
+        let weekend = matches!(weekday, Weekday::Sat | Weekday::Sun);
+        let hours = if weekend {
+            self.weekend.as_ref().or(self.weekday.as_ref())
+        } else {
+            self.weekday.as_ref()
+        };
+
+        let mut hours = hours.cloned().unwrap_or_else(|| vec![8, 12, 18]);
+        if weekend && self.add_weekend_hour {
+            hours.push(22);
+        }
+
+        hours.sort_unstable();
+        hours.dedup();
+        hours
+    }
+
+    /// The remaining hours of interest for the day `now` falls on.
+    pub fn hours(&self, now: DateTime<Tz>) -> Vec<u32> {
+        hours_of_interest(now, Some(self.hours_on(now.weekday())), false)
+    }
+}
+
+/// Parses a comma-separated list of hours, e.g. `7,12,17`, each 0–23.
+pub fn parse_hours(s: &str) -> Result<Vec<u32>> {
+    s.split(',')
+        .map(|hour| {
+            let hour = hour.trim();
+            match hour.parse::<u32>() {
+                Ok(h) if h <= 23 => Ok(h),
+                _ => Err(anyhow!("Hours must be between 0 and 23, got: '{hour}'")),
+            }
+        })
+        .collect()
+}
+
+/// Where to forecast each hour of interest, for reports that follow someone
+/// between places, e.g. home in the morning and the office in the evening.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Itinerary {
+    /// Each location and the hour of the day it applies from, by hour.
+    stops: Vec<(u32, Location)>,
+}
+
+impl Itinerary {
+    /// Stays at `location` all day.
+    pub fn new(location: Location) -> Self {
+        Self {
+            stops: vec![(0, location)],
+        }
+    }
+
+    /// Moves to `location` from `hour` onwards.
+    pub fn then(mut self, hour: u32, location: Location) -> Self {
+        self.stops.retain(|&(h, _)| h != hour);
+        self.stops.push((hour, location));
+        self.stops.sort_by_key(|&(h, _)| h);
+        self
+    }
+
+    /// Where to be at `hour`.
+    pub fn location_at(&self, hour: u32) -> Location {
+        self.stops
+            .iter()
+            .rev()
+            .find(|&&(h, _)| h <= hour)
+            .unwrap_or(&self.stops[0])
+            .1
+    }
+
+    /// Where the day starts, which the current conditions are given for.
+    pub fn start(&self) -> Location {
+        self.location_at(0)
+    }
+
+    /// Each distinct location, starting with [`Itinerary::start`].
+    fn locations(&self) -> Vec<Location> {
+        let mut locations = vec![self.start()];
+        for &(_, location) in &self.stops {
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+        }
+        locations
+    }
+}
+
+/// An ordered chain of forecast sources used to build a [`WeatherReport`].
+/// By default the first source is the primary and the rest are fallbacks
+/// tried in turn when an earlier one fails or times out; see
+/// [`ProviderMode`].
+#[derive(Clone)]
+pub struct WeatherProvider {
+    sources: Vec<Arc<dyn ForecastSource>>,
+    budget: Duration,
+    mode: ProviderMode,
+    schedule: HourSchedule,
+    units: Units,
 }
 
 impl WeatherProvider {
-    pub fn id(&self) -> &'static str {
-        match self {
-            Self::AccuWeather => "accuweather",
-            Self::OpenWeather => "openweather",
+    pub fn new(source: impl ForecastSource + 'static) -> Self {
+        Self::from_sources(vec![Arc::new(source)])
+    }
+
+    fn from_sources(sources: Vec<Arc<dyn ForecastSource>>) -> Self {
+        Self {
+            sources,
+            budget: DEFAULT_FETCH_BUDGET,
+            mode: ProviderMode::default(),
+            schedule: HourSchedule::default(),
+            units: Units::default(),
         }
     }
 
+    /// Sets which hours of the day the report covers.
+    pub fn with_schedule(mut self, schedule: HourSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Sets the units the report is given in.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ProviderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Overrides the total time allowed across the whole chain.
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Ids of the sources in the order they're tried.
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|s| s.id()).collect()
+    }
+
+    /// Whether any provider in the chain needs an [`ApiKey`] to be queried.
+    pub fn requires_api_key(&self) -> bool {
+        self.sources.iter().any(|s| s.requires_api_key())
+    }
+
     pub async fn get_weather(
         &self,
-        cache_mode: CacheMode,
-        api_key: &ApiKey,
-        latitude: f64,
-        longitude: f64,
+        options: &FetchOptions,
+        location: &Location,
+    ) -> Result<WeatherReport> {
+        let weather = self.fetch_forecast(options, location).await?;
+        let now = Utc::now().with_timezone(&weather.timezone);
+
+        Ok(filter_forecast(weather, now, &self.schedule))
+    }
+
+    /// Like [`WeatherProvider::get_weather`], but forecasting each hour of
+    /// interest at wherever `itinerary` puts it. The current conditions,
+    /// hourly series and daily outlook are for where the itinerary starts;
+    /// alerts are for every location. Locations are fetched one at a time.
+    pub async fn get_weather_along(
+        &self,
+        options: &FetchOptions,
+        itinerary: &Itinerary,
+    ) -> Result<WeatherReport> {
+        let mut locations = itinerary.locations().into_iter();
+        let start = locations.next().expect("an itinerary always has a start");
+        let at = |w: &Weather| itinerary.location_at(w.timestamp.hour());
+
+        // The first entry is the current conditions, which always stay.
+        let mut report = self.get_weather(options, &start).await?;
+        let mut hours = report.weather.split_off(1);
+        hours.retain(|w| at(w) == start);
+
+        for location in locations {
+            let other = self.get_weather(options, &location).await?;
+
+            hours.extend(
+                other
+                    .weather
+                    .into_iter()
+                    .skip(1)
+                    .filter(|w| at(w) == location),
+            );
+            for alert in other.alerts {
+                if !report
+                    .alerts
+                    .iter()
+                    .any(|a| consensus::is_same_alert(a, &alert))
+                {
+                    report.alerts.push(alert);
+                }
+            }
+        }
+
+        hours.sort_by_key(|w| w.timestamp);
+        report.weather.append(&mut hours);
+        Ok(report)
+    }
+
+    /// Like [`WeatherProvider::get_weather`], but for the hours of interest on
+    /// `date`. The report still starts with the current conditions, which
+    /// anchor the spoken day labels. Dates up to today get the usual report,
+    /// which [`alexa::forecast_for_date`](crate::alexa::forecast_for_date)
+    /// reads as today's forecast or declines for past dates.
+    pub async fn get_weather_for_date(
+        &self,
+        options: &FetchOptions,
+        location: &Location,
+        date: NaiveDate,
     ) -> Result<WeatherReport> {
+        let weather = self.fetch_forecast(options, location).await?;
+        let now = Utc::now().with_timezone(&weather.timezone);
+
+        if date <= now.date_naive() {
+            return Ok(filter_forecast(weather, now, &self.schedule));
+        }
+
+        Ok(filter_forecast_for_date(weather, date, &self.schedule))
+    }
+
+    async fn fetch_forecast(
+        &self,
+        options: &FetchOptions,
+        location: &Location,
+    ) -> Result<WeatherForecast> {
+        let Location {
+            latitude,
+            longitude,
+        } = *location;
         validate_coordinates(latitude, longitude)
             .with_context(|| format!("Invalid coordinates: lat={latitude}, lon={longitude}"))?;
 
-        let weather = match self {
-            Self::AccuWeather => {
-                accu_weather::get_weather(cache_mode, api_key, latitude, longitude).await
-            }
-            Self::OpenWeather => {
-                open_weather::get_weather(cache_mode, api_key, latitude, longitude).await
-            }
-        }?;
+        let weather = match self.mode {
+            ProviderMode::Fallback => self.fetch(options, location).await,
+            ProviderMode::Consensus => self.fetch_consensus(options, location).await,
+        }?
+        .into_units(self.units);
         debug!("{weather:?}");
 
-        let now = Utc::now().with_timezone(&weather.timezone);
-        let alerts = weather.alerts;
+        Ok(weather)
+    }
+
+    /// Tries each source in order until one succeeds. Each attempt gets an
+    /// even share of whatever budget remains, so a hung primary can't starve
+    /// the fallbacks, while a fast failure leaves more time for the rest.
+    async fn fetch(&self, options: &FetchOptions, location: &Location) -> Result<WeatherForecast> {
+        if self.sources.is_empty() {
+            return Err(anyhow!("No weather providers configured"));
+        }
+
+        let deadline = Instant::now() + self.budget;
+        let mut failures = Vec::with_capacity(self.sources.len());
+
+        for (index, source) in self.sources.iter().enumerate() {
+            let id = source.id();
 
-        let hours_of_interest = hours_of_interest(now, None, false);
+            if source.requires_api_key()
+                && let Err(e) = options.require_api_key(id)
+            {
+                warn!("Skipping {id}: {e}");
+                failures.push(format!("{id}: {e}"));
+                continue;
+            }
 
-        let mut filtered = Vec::with_capacity(1 + hours_of_interest.len());
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                warn!("Skipping {id}: time budget exhausted");
+                failures.push(format!("{id}: time budget exhausted"));
+                continue;
+            }
 
-        filtered.push(weather.current);
+            let attempts_left = (self.sources.len() - index) as u32;
+            let allowed = remaining / attempts_left;
 
-        for hourly_weather in weather.upcoming {
-            if hourly_weather.timestamp.date_naive() > now.date_naive() {
-                trace!("{:?} is no longer relevant", hourly_weather.timestamp);
-                break;
+            match timeout(allowed, source.fetch(location, options)).await {
+                Ok(Ok(weather)) => {
+                    info!("Weather served by {id}");
+                    return Ok(weather);
+                }
+                Ok(Err(e)) => {
+                    warn!("{id} failed: {e:#}");
+                    failures.push(format!("{id}: {e:#}"));
+                }
+                Err(_) => {
+                    warn!("{id} timed out after {allowed:?}");
+                    failures.push(format!("{id}: timed out"));
+                }
             }
+        }
+
+        Err(anyhow!(
+            "All weather providers failed: {}",
+            failures.join("; ")
+        ))
+    }
+
+    /// Queries every source concurrently, each under the full budget, and
+    /// blends whichever forecasts come back.
+    async fn fetch_consensus(
+        &self,
+        options: &FetchOptions,
+        location: &Location,
+    ) -> Result<WeatherForecast> {
+        let mut tasks = JoinSet::new();
+        let mut failures = Vec::new();
 
-            if hourly_weather.timestamp.hour() == now.hour() {
-                trace!("Skipping current hour: {:?}", hourly_weather.timestamp);
+        for (index, source) in self.sources.iter().enumerate() {
+            let id = source.id();
+
+            if source.requires_api_key()
+                && let Err(e) = options.require_api_key(id)
+            {
+                warn!("Skipping {id}: {e}");
+                failures.push(format!("{id}: {e}"));
                 continue;
             }
 
-            if hours_of_interest.contains(&hourly_weather.timestamp.hour()) {
-                debug!("{hourly_weather:?}");
-                filtered.push(hourly_weather);
+            let source = Arc::clone(source);
+            let location = *location;
+            let options = options.clone();
+            let budget = self.budget;
+
+            tasks.spawn(async move {
+                let result = timeout(budget, source.fetch(&location, &options)).await;
+                (index, id, result)
+            });
+        }
+
+        let mut forecasts = Vec::with_capacity(tasks.len());
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, id, Ok(Ok(weather)))) => forecasts.push((index, id, weather)),
+                Ok((_, id, Ok(Err(e)))) => {
+                    warn!("{id} failed: {e:#}");
+                    failures.push(format!("{id}: {e:#}"));
+                }
+                Ok((_, id, Err(_))) => {
+                    warn!("{id} timed out after {:?}", self.budget);
+                    failures.push(format!("{id}: timed out"));
+                }
+                Err(e) => {
+                    warn!("Weather provider task failed: {e}");
+                    failures.push(e.to_string());
+                }
             }
         }
 
-        Ok(WeatherReport {
-            weather: filtered,
-            alerts,
-        })
+        forecasts.sort_by_key(|(index, ..)| *index);
+        let ids: Vec<_> = forecasts.iter().map(|(_, id, _)| *id).collect();
+
+        let weather = consensus::merge(
+            forecasts
+                .into_iter()
+                .map(|(_, id, weather)| (id, weather))
+                .collect(),
+        )
+        .ok_or_else(|| anyhow!("All weather providers failed: {}", failures.join("; ")))?;
+
+        info!("Consensus weather served by {}", ids.join(", "));
+        Ok(weather)
+    }
+}
+
+impl fmt::Debug for WeatherProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeatherProvider")
+            .field("sources", &self.ids())
+            .field("budget", &self.budget)
+            .field("mode", &self.mode)
+            .field("schedule", &self.schedule)
+            .field("units", &self.units)
+            .finish()
+    }
+}
+
+/// Concatenates chains in order, keeping only the first occurrence of each
+/// source.
+impl FromIterator<WeatherProvider> for WeatherProvider {
+    fn from_iter<I: IntoIterator<Item = WeatherProvider>>(iter: I) -> Self {
+        let mut sources: Vec<Arc<dyn ForecastSource>> = Vec::new();
+
+        for source in iter.into_iter().flat_map(|p| p.sources) {
+            if !sources.iter().any(|s| s.id() == source.id()) {
+                sources.push(source);
+            }
+        }
+
+        Self::from_sources(sources)
     }
 }
 
+/// Parses a comma-separated list of provider ids, e.g.
+/// `openweather,accuweather,nws`.
 impl FromStr for WeatherProvider {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if Self::AccuWeather.id().eq_ignore_ascii_case(s) {
-            Ok(Self::AccuWeather)
-        } else if Self::OpenWeather.id().eq_ignore_ascii_case(s) {
-            Ok(Self::OpenWeather)
-        } else {
-            Err(anyhow!("Unknown weather provider: {}", s))
+        let ids: Vec<&str> = s.split(',').map(str::trim).collect();
+        if ids.iter().any(|id| id.is_empty()) {
+            return Err(anyhow!("Invalid weather provider list: {s:?}"));
+        }
+
+        ids.into_iter()
+            .map(|id| {
+                source::find_source(id)
+                    .map(|source| Self::from_sources(vec![source]))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Unknown weather provider: {} (expected one of: {})",
+                            id,
+                            registered_ids().join(", ")
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Narrows a forecast to the current conditions plus today's remaining hours
+/// of interest.
+fn filter_forecast(
+    weather: WeatherForecast,
+    now: DateTime<Tz>,
+    schedule: &HourSchedule,
+) -> WeatherReport {
+    let alerts = weather.alerts;
+
+    // Once today's hours have passed, look ahead to tomorrow's instead.
+    let mut day = now.date_naive();
+    let mut hours_of_interest = schedule.hours(now);
+    if hours_of_interest.is_empty()
+        && let Some(tomorrow) = day.succ_opt()
+    {
+        debug!("Today's hours of interest have passed, forecasting {tomorrow}");
+        day = tomorrow;
+        hours_of_interest = schedule.hours_on(tomorrow.weekday());
+    }
+
+    let mut filtered = Vec::with_capacity(1 + hours_of_interest.len());
+
+    filtered.push(weather.current);
+
+    for hourly_weather in &weather.upcoming {
+        let date = hourly_weather.timestamp.date_naive();
+
+        if date < day {
+            continue;
+        }
+
+        if date > day {
+            trace!("{:?} is no longer relevant", hourly_weather.timestamp);
+            break;
+        }
+
+        if date == now.date_naive() && hourly_weather.timestamp.hour() == now.hour() {
+            trace!("Skipping current hour: {:?}", hourly_weather.timestamp);
+            continue;
         }
+
+        if hours_of_interest.contains(&hourly_weather.timestamp.hour()) {
+            debug!("{hourly_weather:?}");
+            filtered.push(hourly_weather.clone());
+        }
+    }
+
+    WeatherReport {
+        weather: filtered,
+        hourly: weather.upcoming,
+        daily: weather
+            .daily
+            .into_iter()
+            .filter(|daily| daily.date >= now.date_naive())
+            .collect(),
+        alerts,
+    }
+}
+
+/// Narrows a forecast to the current conditions plus the hours of interest on
+/// a later `date`, with that day's outlook and the alerts in effect on it.
+fn filter_forecast_for_date(
+    weather: WeatherForecast,
+    date: NaiveDate,
+    schedule: &HourSchedule,
+) -> WeatherReport {
+    let hours_of_interest = schedule.hours_on(date.weekday());
+    debug!("Forecasting {date}, hours of interest: {hours_of_interest:?}");
+
+    let mut filtered = vec![weather.current];
+    filtered.extend(
+        weather
+            .upcoming
+            .iter()
+            .filter(|w| {
+                w.timestamp.date_naive() == date && hours_of_interest.contains(&w.timestamp.hour())
+            })
+            .cloned(),
+    );
+
+    WeatherReport {
+        weather: filtered,
+        hourly: weather.upcoming,
+        daily: weather
+            .daily
+            .into_iter()
+            .filter(|daily| daily.date == date)
+            .collect(),
+        alerts: weather
+            .alerts
+            .into_iter()
+            .filter(|alert| alert.start.date_naive() <= date && date <= alert.end.date_naive())
+            .collect(),
     }
 }
 
+/// Alerts come from a separate request, so failing to get or parse them
+/// leaves the forecast without alerts rather than failing it.
+pub(crate) fn alerts_or_empty(alerts: Result<Vec<WeatherAlert>>) -> Vec<WeatherAlert> {
+    alerts.unwrap_or_else(|e| {
+        warn!("Continuing without alerts: {e:#}");
+        Vec::new()
+    })
+}
+
+/// Drops alerts that have already ended, start more than five days out, or
+/// have an end before their start.
+pub(crate) fn filter_alerts(alerts: Vec<WeatherAlert>, timezone: Tz) -> Vec<WeatherAlert> {
+    let now = Utc::now().with_timezone(&timezone);
+    let cutoff_time = now + chrono::Duration::days(5);
+
+    alerts
+        .into_iter()
+        .filter(|alert| alert.end > now && alert.start <= cutoff_time && alert.start <= alert.end)
+        .collect()
+}
+
 pub fn hours_of_interest(
     current_time: DateTime<Tz>,
     hours: Option<Vec<u32>>,
@@ -196,13 +883,15 @@ pub fn hours_of_interest(
     }
 
     hours.sort_unstable();
+    hours.dedup();
 
-    for n in 0..hours.len() {
-        if current_time.hour() + 1 < hours[n] {
-            hours = hours.split_off(n);
-            break;
-        }
-    }
+    // Hours that are less than an hour away are covered by the current
+    // conditions; if none remain, today's hours have all passed.
+    let remaining = hours
+        .iter()
+        .position(|&hour| current_time.hour() + 1 < hour)
+        .unwrap_or(hours.len());
+    let hours = hours.split_off(remaining);
 
     debug!("Hours of Interest: {hours:?}");
 
@@ -241,6 +930,7 @@ pub fn validate_coordinates(latitude: f64, longitude: f64) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use jluszcz_rust_utils::cache::CacheMode;
 
     const SECRET: &str = "super-secret-key-value";
 
@@ -269,4 +959,632 @@ mod tests {
         let key = ApiKey::new(SECRET).unwrap();
         assert_eq!(key.as_str(), SECRET);
     }
+
+    fn new_york(day: u32, hour: u32) -> DateTime<Tz> {
+        use chrono::TimeZone;
+
+        // January 2024: the 15th is a Monday, the 20th a Saturday.
+        Tz::America__New_York
+            .with_ymd_and_hms(2024, 1, day, hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn units_from_str_ignores_case() -> Result<()> {
+        assert_eq!(Units::Metric, "Metric".parse()?);
+        assert_eq!(Units::Imperial, " imperial ".parse()?);
+        assert!("kelvin".parse::<Units>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn forecast_converts_to_metric() {
+        let mut current = Weather::test(Some("sunny"));
+        current.temp = 23.0;
+        current.apparent_temp = Some(50.0);
+        current.wind_speed = Some(10.0);
+
+        let forecast = WeatherForecast {
+            current,
+            upcoming: Vec::new(),
+            daily: vec![DailyWeather {
+                date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+                summary: "sunny".to_string(),
+                units: Units::Imperial,
+                high: 212.0,
+                low: 32.0,
+            }],
+            timezone: Tz::UTC,
+            alerts: Vec::new(),
+        }
+        .into_units(Units::Metric);
+
+        let current = &forecast.current;
+        assert_eq!(Units::Metric, current.units);
+        assert!((current.temp - -5.0).abs() < 1e-9);
+        assert!((current.apparent_temp.unwrap() - 10.0).abs() < 1e-9);
+        assert!((current.wind_speed.unwrap() - 16.09344).abs() < 1e-9);
+        assert_eq!(None, current.wind_gust);
+
+        let day = &forecast.daily[0];
+        assert_eq!(Units::Metric, day.units);
+        assert!((day.high - 100.0).abs() < 1e-9);
+        assert!(day.low.abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_hours_accepts_valid_list() -> Result<()> {
+        assert_eq!(vec![7, 12, 17], parse_hours("7,12,17")?);
+        assert_eq!(vec![0, 23], parse_hours("0, 23")?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_hours_rejects_out_of_range() {
+        assert!(parse_hours("7,24").is_err());
+        assert!(parse_hours("-1").is_err());
+        assert!(parse_hours("7,,12").is_err());
+        assert!(parse_hours("noon").is_err());
+    }
+
+    #[test]
+    fn schedule_defaults() {
+        let schedule = HourSchedule::default();
+
+        assert_eq!(vec![8, 12, 18], schedule.hours(new_york(15, 6)));
+        assert_eq!(vec![12, 18], schedule.hours(new_york(15, 9)));
+    }
+
+    #[test]
+    fn schedule_uses_weekend_hours_on_weekends() {
+        let schedule = HourSchedule {
+            weekday: Some(vec![7, 12, 17]),
+            weekend: Some(vec![10, 14]),
+            add_weekend_hour: false,
+        };
+
+        assert_eq!(vec![7, 12, 17], schedule.hours(new_york(15, 5)));
+        assert_eq!(vec![10, 14], schedule.hours(new_york(20, 5)));
+    }
+
+    #[test]
+    fn schedule_weekend_falls_back_to_weekday_hours() {
+        let schedule = HourSchedule {
+            weekday: Some(vec![7, 12, 17]),
+            weekend: None,
+            add_weekend_hour: true,
+        };
+
+        assert_eq!(vec![7, 12, 17], schedule.hours(new_york(15, 5)));
+        assert_eq!(vec![7, 12, 17, 22], schedule.hours(new_york(20, 5)));
+    }
+
+    #[test]
+    fn schedule_weekend_hour_is_not_repeated() {
+        let schedule = HourSchedule {
+            weekday: Some(vec![12, 22]),
+            weekend: None,
+            add_weekend_hour: true,
+        };
+
+        assert_eq!(vec![12, 22], schedule.hours(new_york(20, 5)));
+    }
+
+    #[test]
+    fn schedule_is_empty_once_hours_have_passed() {
+        let schedule = HourSchedule::default();
+
+        assert_eq!(vec![18], schedule.hours(new_york(15, 16)));
+        assert!(schedule.hours(new_york(15, 17)).is_empty());
+        assert!(schedule.hours(new_york(15, 21)).is_empty());
+    }
+
+    #[test]
+    fn schedule_hours_on_includes_every_hour() {
+        let schedule = HourSchedule {
+            weekday: Some(vec![17, 7, 12]),
+            weekend: None,
+            add_weekend_hour: true,
+        };
+
+        assert_eq!(vec![7, 12, 17], schedule.hours_on(Weekday::Mon));
+        assert_eq!(vec![7, 12, 17, 22], schedule.hours_on(Weekday::Sun));
+    }
+
+    #[test]
+    fn filter_forecast_looks_ahead_to_tomorrow_in_the_evening() {
+        let now = new_york(19, 20);
+        let at = |day, hour| Weather {
+            timestamp: new_york(day, hour),
+            ..Weather::test(Some("sunny"))
+        };
+        let forecast = WeatherForecast {
+            current: at(19, 20),
+            upcoming: (21..24)
+                .map(|hour| at(19, hour))
+                .chain((0..24).map(|hour| at(20, hour)))
+                .chain((0..24).map(|hour| at(21, hour)))
+                .collect(),
+            daily: Vec::new(),
+            timezone: Tz::America__New_York,
+            alerts: Vec::new(),
+        };
+        let schedule = HourSchedule {
+            weekend: Some(vec![10, 14]),
+            ..HourSchedule::default()
+        };
+
+        // Friday evening, so Saturday's weekend hours apply.
+        let report = filter_forecast(forecast, now, &schedule);
+        let hours: Vec<_> = report
+            .weather
+            .iter()
+            .map(|w| (w.timestamp.day(), w.timestamp.hour()))
+            .collect();
+
+        assert_eq!(vec![(19, 20), (20, 10), (20, 14)], hours);
+    }
+
+    #[test]
+    fn filter_forecast_for_date_keeps_that_days_hours() {
+        let at = |day, hour| Weather {
+            timestamp: new_york(day, hour),
+            ..Weather::test(Some("sunny"))
+        };
+        let day = |day| DailyWeather {
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            summary: "sunny".to_string(),
+            units: Units::Imperial,
+            high: 40.0,
+            low: 30.0,
+        };
+        let alert = |start, end| WeatherAlert {
+            event: "Winter Storm Warning".to_string(),
+            sender_name: "NWS".to_string(),
+            start: new_york(start, 0),
+            end: new_york(end, 12),
+            description: String::new(),
+        };
+        let forecast = WeatherForecast {
+            current: at(18, 9),
+            upcoming: (18..22)
+                .flat_map(|day| (0..24).map(move |hour| (day, hour)))
+                .map(|(day, hour)| at(day, hour))
+                .collect(),
+            daily: (18..23).map(day).collect(),
+            timezone: Tz::America__New_York,
+            alerts: vec![alert(18, 19), alert(19, 21), alert(21, 22)],
+        };
+        let schedule = HourSchedule {
+            weekend: Some(vec![10, 14]),
+            ..HourSchedule::default()
+        };
+
+        // Saturday, so the weekend hours apply.
+        let saturday = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+        let report = filter_forecast_for_date(forecast, saturday, &schedule);
+        let hours: Vec<_> = report
+            .weather
+            .iter()
+            .map(|w| (w.timestamp.day(), w.timestamp.hour()))
+            .collect();
+
+        assert_eq!(vec![(18, 9), (20, 10), (20, 14)], hours);
+        assert_eq!(1, report.daily.len());
+        assert_eq!(saturday, report.daily[0].date);
+        assert_eq!(1, report.alerts.len());
+        assert_eq!(new_york(19, 0), report.alerts[0].start);
+    }
+
+    #[test]
+    fn filter_forecast_keeps_scheduled_hours() {
+        let now = new_york(15, 5);
+        let at = |hour| Weather {
+            timestamp: new_york(15, hour),
+            ..Weather::test(Some("sunny"))
+        };
+        let forecast = WeatherForecast {
+            current: at(5),
+            upcoming: (6..24).map(at).collect(),
+            daily: Vec::new(),
+            timezone: Tz::America__New_York,
+            alerts: Vec::new(),
+        };
+        let schedule = HourSchedule {
+            weekday: Some(vec![7, 12, 17]),
+            ..HourSchedule::default()
+        };
+
+        let report = filter_forecast(forecast, now, &schedule);
+        let hours: Vec<_> = report.weather.iter().map(|w| w.timestamp.hour()).collect();
+
+        assert_eq!(vec![5, 7, 12, 17], hours);
+
+        // The full hourly series is kept for questions about other times.
+        assert_eq!(18, report.hourly.len());
+    }
+
+    enum StubOutcome {
+        Serve(&'static str),
+        Fail,
+        Hang,
+    }
+
+    /// Source with a scripted outcome so `WeatherProvider` can be exercised
+    /// without the network.
+    struct StubSource {
+        id: &'static str,
+        outcome: StubOutcome,
+    }
+
+    impl StubSource {
+        fn serving(id: &'static str) -> Self {
+            Self {
+                id,
+                outcome: StubOutcome::Serve(id),
+            }
+        }
+    }
+
+    impl ForecastSource for StubSource {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn requires_api_key(&self) -> bool {
+            false
+        }
+
+        fn fetch<'a>(
+            &'a self,
+            _location: &'a Location,
+            _options: &'a FetchOptions,
+        ) -> ForecastFuture<'a> {
+            Box::pin(async move {
+                match self.outcome {
+                    StubOutcome::Serve(summary) => Ok(WeatherForecast {
+                        current: Weather::test(Some(summary)),
+                        upcoming: Vec::new(),
+                        daily: Vec::new(),
+                        timezone: Tz::UTC,
+                        alerts: Vec::new(),
+                    }),
+                    StubOutcome::Fail => Err(anyhow!("stub failure")),
+                    StubOutcome::Hang => {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        Err(anyhow!("stub hung"))
+                    }
+                }
+            })
+        }
+    }
+
+    /// Serves the next two days hour by hour, each summarized with the
+    /// latitude asked for, so tests can tell which location an hour is from.
+    struct LatitudeSource;
+
+    impl ForecastSource for LatitudeSource {
+        fn id(&self) -> &'static str {
+            "latitude"
+        }
+
+        fn requires_api_key(&self) -> bool {
+            false
+        }
+
+        fn fetch<'a>(
+            &'a self,
+            location: &'a Location,
+            _options: &'a FetchOptions,
+        ) -> ForecastFuture<'a> {
+            Box::pin(async move {
+                let now = Utc::now().with_timezone(&Tz::UTC);
+                let at = |hours| Weather {
+                    timestamp: now + chrono::Duration::hours(hours),
+                    ..Weather::test(Some(location.latitude.to_string()))
+                };
+                // The same advisory, timed a little differently per location.
+                let start = now + chrono::Duration::hours(location.latitude as i64 - 39);
+                let alert = WeatherAlert {
+                    event: "Wind Advisory".to_string(),
+                    sender_name: location.latitude.to_string(),
+                    start,
+                    end: start + chrono::Duration::hours(6),
+                    description: String::new(),
+                };
+
+                Ok(WeatherForecast {
+                    current: at(0),
+                    upcoming: (1..48).map(at).collect(),
+                    daily: Vec::new(),
+                    timezone: Tz::UTC,
+                    alerts: vec![alert],
+                })
+            })
+        }
+    }
+
+    fn chain(sources: Vec<StubSource>) -> WeatherProvider {
+        sources.into_iter().map(WeatherProvider::new).collect()
+    }
+
+    const LOCATION: Location = Location {
+        latitude: 40.7128,
+        longitude: -74.0060,
+    };
+
+    fn options() -> FetchOptions {
+        FetchOptions::new(CacheMode::Disabled, None)
+    }
+
+    #[test]
+    fn provider_requires_api_key() {
+        let requires = |id: &str| WeatherProvider::from_str(id).unwrap().requires_api_key();
+
+        assert!(requires("accuweather"));
+        assert!(requires("openweather"));
+        assert!(!requires("nws"));
+        assert!(!requires("openmeteo"));
+    }
+
+    #[test]
+    fn provider_from_str_ignores_case() {
+        assert_eq!(
+            vec!["openweather"],
+            WeatherProvider::from_str("OpenWeather").unwrap().ids()
+        );
+    }
+
+    #[test]
+    fn provider_from_str_lists_registered_ids() {
+        let err = WeatherProvider::from_str("invalid-provider")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("accuweather, nws, openmeteo, openweather"),
+            "Unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn registered_ids_include_builtins() {
+        let ids = registered_ids();
+        for id in ["accuweather", "nws", "openmeteo", "openweather"] {
+            assert!(ids.contains(&id), "{id} missing from {ids:?}");
+        }
+    }
+
+    #[test]
+    fn register_source_rejects_duplicate_id() {
+        assert!(register_source(StubSource::serving("OpenWeather")).is_err());
+    }
+
+    #[tokio::test]
+    async fn registered_source_is_selectable() -> Result<()> {
+        register_source(StubSource::serving("stub"))?;
+
+        let provider = WeatherProvider::from_str("stub")?;
+        let report = provider.get_weather(&options(), &LOCATION).await?;
+
+        assert_eq!("stub", report.weather[0].summary);
+
+        Ok(())
+    }
+
+    #[test]
+    fn itinerary_location_at() {
+        let home = Location::new(40.0, -74.0);
+        let work = Location::new(41.0, -74.0);
+        let itinerary = Itinerary::new(home).then(12, work).then(20, home);
+
+        assert_eq!(home, itinerary.start());
+        assert_eq!(home, itinerary.location_at(11));
+        assert_eq!(work, itinerary.location_at(12));
+        assert_eq!(work, itinerary.location_at(19));
+        assert_eq!(home, itinerary.location_at(20));
+        assert_eq!(vec![home, work], itinerary.locations());
+    }
+
+    #[tokio::test]
+    async fn get_weather_along_picks_location_per_hour() -> Result<()> {
+        let home = Location::new(40.0, -74.0);
+        let work = Location::new(41.0, -74.0);
+        let provider = WeatherProvider::new(LatitudeSource).with_schedule(HourSchedule {
+            weekday: Some((0..24).collect()),
+            weekend: None,
+            add_weekend_hour: false,
+        });
+
+        let report = provider
+            .get_weather_along(&options(), &Itinerary::new(home).then(12, work))
+            .await?;
+
+        let [current, hours @ ..] = report.weather.as_slice() else {
+            panic!("Missing current conditions");
+        };
+        assert_eq!("40", current.summary);
+        assert!(!hours.is_empty());
+        assert!(hours.is_sorted_by_key(|w| w.timestamp));
+        for w in hours {
+            let expected = if w.timestamp.hour() < 12 { "40" } else { "41" };
+            assert_eq!(expected, w.summary, "{:?}", w.timestamp);
+        }
+        assert_eq!(1, report.alerts.len(), "{:?}", report.alerts);
+        assert_eq!("40", report.alerts[0].sender_name);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_weather_without_required_api_key_fails() {
+        let result = WeatherProvider::new(open_weather::OpenWeather)
+            .get_weather(&options(), &LOCATION)
+            .await;
+
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("requires an API key"),
+            "Unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_weather_rejects_invalid_coordinates() {
+        let result = WeatherProvider::new(StubSource::serving("unregistered"))
+            .get_weather(&options(), &Location::new(91.0, 0.0))
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_weather_falls_back_after_failure() -> Result<()> {
+        let provider = chain(vec![
+            StubSource {
+                id: "primary",
+                outcome: StubOutcome::Fail,
+            },
+            StubSource::serving("fallback"),
+        ]);
+
+        let report = provider.get_weather(&options(), &LOCATION).await?;
+        assert_eq!("fallback", report.weather[0].summary);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_weather_skips_providers_missing_api_key() -> Result<()> {
+        let provider: WeatherProvider = [
+            WeatherProvider::new(open_weather::OpenWeather),
+            WeatherProvider::new(StubSource::serving("fallback")),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = provider.get_weather(&options(), &LOCATION).await?;
+        assert_eq!("fallback", report.weather[0].summary);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_weather_falls_back_after_timeout() -> Result<()> {
+        let provider = chain(vec![
+            StubSource {
+                id: "primary",
+                outcome: StubOutcome::Hang,
+            },
+            StubSource::serving("fallback"),
+        ])
+        .with_budget(Duration::from_millis(100));
+
+        let report = provider.get_weather(&options(), &LOCATION).await?;
+        assert_eq!("fallback", report.weather[0].summary);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_weather_respects_total_budget() {
+        let provider = chain(vec![
+            StubSource {
+                id: "first",
+                outcome: StubOutcome::Hang,
+            },
+            StubSource {
+                id: "second",
+                outcome: StubOutcome::Hang,
+            },
+        ])
+        .with_budget(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let err = provider
+            .get_weather(&options(), &LOCATION)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(err.contains("first: timed out"), "Unexpected error: {err}");
+        assert!(err.contains("second: timed out"), "Unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn get_weather_consensus_blends_every_provider() -> Result<()> {
+        let provider = chain(vec![
+            StubSource::serving("sunny"),
+            StubSource {
+                id: "broken",
+                outcome: StubOutcome::Fail,
+            },
+            StubSource::serving("cloudy"),
+        ])
+        .with_mode(ProviderMode::Consensus);
+
+        let report = provider.get_weather(&options(), &LOCATION).await?;
+        let spread = report.weather[0].spread.as_ref().unwrap();
+
+        assert_eq!("sunny", report.weather[0].summary);
+        assert_eq!(vec!["sunny", "cloudy"], spread.providers);
+        assert_eq!(0.5, spread.summary_agreement);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_weather_consensus_fails_when_every_provider_fails() {
+        let provider = chain(vec![
+            StubSource {
+                id: "first",
+                outcome: StubOutcome::Fail,
+            },
+            StubSource {
+                id: "second",
+                outcome: StubOutcome::Hang,
+            },
+        ])
+        .with_budget(Duration::from_millis(50))
+        .with_mode(ProviderMode::Consensus);
+
+        let err = provider
+            .get_weather(&options(), &LOCATION)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("first: stub failure"),
+            "Unexpected error: {err}"
+        );
+        assert!(err.contains("second: timed out"), "Unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn get_weather_reports_every_failure() {
+        let provider = chain(vec![
+            StubSource {
+                id: "first",
+                outcome: StubOutcome::Fail,
+            },
+            StubSource {
+                id: "second",
+                outcome: StubOutcome::Fail,
+            },
+        ]);
+
+        let err = provider
+            .get_weather(&options(), &LOCATION)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("first: stub failure"),
+            "Unexpected error: {err}"
+        );
+        assert!(
+            err.contains("second: stub failure"),
+            "Unexpected error: {err}"
+        );
+    }
 }
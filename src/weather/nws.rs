@@ -0,0 +1,520 @@
+use crate::weather::{
+    DailyWeather, FetchOptions, ForecastFuture, ForecastSource, Location, Units, Weather,
+    WeatherAlert, WeatherForecast, alerts_or_empty, filter_alerts,
+};
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, FixedOffset};
+use chrono_tz::Tz;
+use jluszcz_rust_utils::cache::{CacheMode, dated_cache_path, try_cached_query_json};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// api.weather.gov may reject requests without a User-Agent identifying the
+/// application and how to reach its maintainers.
+const USER_AGENT: &str = concat!(
+    "jakesky/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/jluszcz/JakeSky-rs)"
+);
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .expect("Failed to build NWS HTTP client")
+});
+
+#[derive(Deserialize, Debug)]
+struct PointsResponse {
+    properties: PointsProperties,
+}
+
+#[derive(Deserialize, Debug)]
+struct PointsProperties {
+    /// Twelve-hour day and night periods.
+    forecast: String,
+
+    #[serde(alias = "forecastHourly")]
+    forecast_hourly: String,
+
+    #[serde(alias = "timeZone")]
+    timezone: String,
+}
+
+/// Both the hourly and the twelve-hour forecasts share this shape.
+#[derive(Deserialize, Debug)]
+struct ForecastResponse {
+    properties: ForecastProperties,
+}
+
+#[derive(Deserialize, Debug)]
+struct ForecastProperties {
+    periods: Vec<Period>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Period {
+    #[serde(alias = "startTime")]
+    start_time: DateTime<FixedOffset>,
+
+    temperature: f64,
+
+    #[serde(alias = "isDaytime", default)]
+    is_daytime: bool,
+
+    #[serde(alias = "shortForecast")]
+    short_forecast: String,
+
+    #[serde(alias = "probabilityOfPrecipitation", default)]
+    precip_probability: Option<Quantity>,
+
+    /// Free text such as "12 mph" or "10 to 15 mph".
+    #[serde(alias = "windSpeed", default)]
+    wind_speed: Option<String>,
+
+    /// Compass point such as "NW".
+    #[serde(alias = "windDirection", default)]
+    wind_direction: Option<String>,
+}
+
+/// A measurement with a WMO unit code; `value` is null when unknown.
+#[derive(Deserialize, Debug)]
+struct Quantity {
+    value: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AlertsResponse {
+    #[serde(default)]
+    features: Vec<AlertFeature>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AlertFeature {
+    properties: Alert,
+}
+
+#[derive(Deserialize, Debug)]
+struct Alert {
+    event: String,
+    #[serde(alias = "senderName")]
+    sender_name: String,
+    effective: DateTime<FixedOffset>,
+    #[serde(default)]
+    onset: Option<DateTime<FixedOffset>>,
+    expires: DateTime<FixedOffset>,
+    #[serde(default)]
+    ends: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    #[allow(dead_code)] // Part of API response, not currently used
+    severity: Option<String>,
+    #[serde(default)]
+    #[allow(dead_code)] // Part of API response, not currently used
+    urgency: Option<String>,
+}
+
+fn normalize_weather(weather: &str) -> String {
+    weather.replace(" And ", " and ")
+}
+
+/// The top of the range for speeds like "10 to 15 mph".
+fn parse_wind_speed(speed: &str) -> Option<f64> {
+    speed
+        .split_whitespace()
+        .rev()
+        .find_map(|word| word.parse::<f64>().ok())
+}
+
+/// Degrees clockwise from north for a 16-point compass direction.
+fn parse_wind_direction(direction: &str) -> Option<f64> {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+
+    POINTS
+        .iter()
+        .position(|p| p.eq_ignore_ascii_case(direction.trim()))
+        .map(|index| index as f64 * 22.5)
+}
+
+fn period_to_weather(period: &Period, timezone: Tz) -> Weather {
+    Weather {
+        timestamp: period.start_time.with_timezone(&timezone),
+        summary: normalize_weather(&period.short_forecast),
+        units: Units::Imperial,
+        temp: period.temperature,
+        apparent_temp: None,
+        precip_probability: period
+            .precip_probability
+            .as_ref()
+            .and_then(|p| p.value)
+            .map(|p| p / 100.0),
+        precip_amount: None,
+        wind_speed: period.wind_speed.as_deref().and_then(parse_wind_speed),
+        wind_gust: None,
+        wind_bearing: period
+            .wind_direction
+            .as_deref()
+            .and_then(parse_wind_direction),
+        spread: None,
+    }
+}
+
+/// Alerts may omit `onset`/`ends`, in which case the CAP `effective`/`expires`
+/// times bound the alert instead.
+fn to_weather_alert(alert: Alert, timezone: Tz) -> WeatherAlert {
+    WeatherAlert {
+        event: alert.event,
+        sender_name: alert.sender_name,
+        start: alert
+            .onset
+            .unwrap_or(alert.effective)
+            .with_timezone(&timezone),
+        end: alert.ends.unwrap_or(alert.expires).with_timezone(&timezone),
+        description: alert.description,
+    }
+}
+
+fn parse_hourly(response: ForecastResponse, timezone: Tz) -> Result<Vec<Weather>> {
+    if response.properties.periods.is_empty() {
+        return Err(anyhow!("NWS API returned no hourly forecast periods"));
+    }
+
+    Ok(response
+        .properties
+        .periods
+        .iter()
+        .map(|p| period_to_weather(p, timezone))
+        .collect())
+}
+
+/// Pairs each daytime period with the night that follows it, which gives the
+/// low. A forecast fetched in the evening starts with a night period, so it
+/// has no entry for today.
+fn parse_daily(response: ForecastResponse, timezone: Tz) -> Vec<DailyWeather> {
+    let periods = &response.properties.periods;
+
+    periods
+        .iter()
+        .zip(periods.iter().skip(1))
+        .filter(|(day, night)| day.is_daytime && !night.is_daytime)
+        .map(|(day, night)| DailyWeather {
+            date: day.start_time.with_timezone(&timezone).date_naive(),
+            summary: normalize_weather(&day.short_forecast),
+            units: Units::Imperial,
+            high: day.temperature,
+            low: night.temperature,
+        })
+        .collect()
+}
+
+fn parse_alerts(response: AlertsResponse, timezone: Tz) -> Vec<WeatherAlert> {
+    response
+        .features
+        .into_iter()
+        .map(|f| to_weather_alert(f.properties, timezone))
+        .collect()
+}
+
+/// NWS only accepts up to four decimal places and redirects anything finer.
+fn format_point(latitude: f64, longitude: f64) -> String {
+    format!("{latitude:.4},{longitude:.4}")
+}
+
+/// Like `query::http_get`, but identifying ourselves with [`USER_AGENT`].
+async fn http_get(url: &str, params: &[(&str, &str)]) -> Result<String> {
+    let response = CLIENT
+        .get(url)
+        .query(params)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(response.text().await?)
+}
+
+async fn query_points(latitude: f64, longitude: f64) -> Result<String> {
+    http_get(
+        &format!(
+            "https://api.weather.gov/points/{}",
+            format_point(latitude, longitude)
+        ),
+        &[],
+    )
+    .await
+    .with_context(|| "Failed to make HTTP request to NWS points API")
+}
+
+async fn query_hourly(url: &str) -> Result<String> {
+    http_get(url, &[])
+        .await
+        .with_context(|| "Failed to make HTTP request to NWS hourly forecast API")
+}
+
+async fn query_daily(url: &str) -> Result<String> {
+    http_get(url, &[])
+        .await
+        .with_context(|| "Failed to make HTTP request to NWS forecast API")
+}
+
+async fn query_alerts(latitude: f64, longitude: f64) -> Result<String> {
+    let point = format_point(latitude, longitude);
+    http_get(
+        "https://api.weather.gov/alerts/active",
+        &[("point", point.as_str())],
+    )
+    .await
+    .with_context(|| "Failed to make HTTP request to NWS alerts API")
+}
+
+pub async fn get_weather(
+    cache_mode: CacheMode,
+    latitude: f64,
+    longitude: f64,
+) -> Result<WeatherForecast> {
+    let token_suffix = format!("{latitude:.1}_{longitude:.1}");
+
+    let points_cache_path = dated_cache_path(&format!("nws-points_{token_suffix}"));
+    let hourly_cache_path = dated_cache_path(&format!("nws-hourly_{token_suffix}"));
+    let daily_cache_path = dated_cache_path(&format!("nws-daily_{token_suffix}"));
+    let alerts_cache_path = dated_cache_path(&format!("nws-alerts_{token_suffix}"));
+
+    let points: PointsResponse = try_cached_query_json(cache_mode, &points_cache_path, || {
+        query_points(latitude, longitude)
+    })
+    .await
+    .with_context(|| {
+        format!("Failed to get NWS grid point for coordinates {latitude}, {longitude}")
+    })?;
+
+    let timezone = Tz::from_str(&points.properties.timezone).with_context(|| {
+        format!(
+            "Failed to parse timezone '{}' from NWS points API",
+            points.properties.timezone
+        )
+    })?;
+
+    let hourly: ForecastResponse = try_cached_query_json(cache_mode, &hourly_cache_path, || {
+        query_hourly(&points.properties.forecast_hourly)
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get NWS hourly forecast from {}",
+            points.properties.forecast_hourly
+        )
+    })?;
+
+    let daily: ForecastResponse = try_cached_query_json(cache_mode, &daily_cache_path, || {
+        query_daily(&points.properties.forecast)
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get NWS forecast from {}",
+            points.properties.forecast
+        )
+    })?;
+
+    let alerts: Result<AlertsResponse> =
+        try_cached_query_json(cache_mode, &alerts_cache_path, || {
+            query_alerts(latitude, longitude)
+        })
+        .await
+        .with_context(|| {
+            format!("Failed to get NWS alerts for coordinates {latitude}, {longitude}")
+        });
+
+    let mut weather =
+        parse_hourly(hourly, timezone).with_context(|| "Failed to parse NWS hourly forecast")?;
+
+    Ok(WeatherForecast {
+        timezone,
+        current: weather.remove(0),
+        upcoming: weather,
+        daily: parse_daily(daily, timezone),
+        alerts: filter_alerts(
+            alerts_or_empty(alerts.map(|alerts| parse_alerts(alerts, timezone))),
+            timezone,
+        ),
+    })
+}
+
+pub struct Nws;
+
+impl ForecastSource for Nws {
+    fn id(&self) -> &'static str {
+        "nws"
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        location: &'a Location,
+        options: &'a FetchOptions,
+    ) -> ForecastFuture<'a> {
+        Box::pin(async move {
+            get_weather(options.cache_mode, location.latitude, location.longitude).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::Router;
+    use axum::http::HeaderMap;
+    use axum::routing::get;
+    use chrono::Timelike;
+    use tokio::net::TcpListener;
+
+    const POINTS_RESPONSE: &str = r#"{"@context":["https://geojson.org/geojson-ld/geojson-context.jsonld",{"@version":"1.1","wx":"https://api.weather.gov/ontology#","s":"https://schema.org/","geo":"http://www.opengis.net/ont/geosparql#","unit":"http://codes.wmo.int/common/unit/","@vocab":"https://api.weather.gov/ontology#"}],"id":"https://api.weather.gov/points/40.7128,-74.006","type":"Feature","geometry":{"type":"Point","coordinates":[-74.006,40.7128]},"properties":{"@id":"https://api.weather.gov/points/40.7128,-74.006","@type":"wx:Point","cwa":"OKX","forecastOffice":"https://api.weather.gov/offices/OKX","gridId":"OKX","gridX":33,"gridY":35,"forecast":"https://api.weather.gov/gridpoints/OKX/33,35/forecast","forecastHourly":"https://api.weather.gov/gridpoints/OKX/33,35/forecast/hourly","forecastGridData":"https://api.weather.gov/gridpoints/OKX/33,35","observationStations":"https://api.weather.gov/gridpoints/OKX/33,35/stations","relativeLocation":{"type":"Feature","geometry":{"type":"Point","coordinates":[-74.0104,40.7095]},"properties":{"city":"New York","state":"NY","distance":{"unitCode":"wmoUnit:m","value":508.2},"bearing":{"unitCode":"wmoUnit:degree_(angle)","value":45}}},"forecastZone":"https://api.weather.gov/zones/forecast/NYZ072","county":"https://api.weather.gov/zones/county/NYC061","fireWeatherZone":"https://api.weather.gov/zones/fire/NYZ212","timeZone":"America/New_York","radarStation":"KDIX"}}"#;
+
+    const HOURLY_RESPONSE: &str = r#"{"@context":["https://geojson.org/geojson-ld/geojson-context.jsonld",{"@version":"1.1","wx":"https://api.weather.gov/ontology#","geo":"http://www.opengis.net/ont/geosparql#","unit":"http://codes.wmo.int/common/unit/","@vocab":"https://api.weather.gov/ontology#"}],"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[-74.0177,40.7203],[-74.0223,40.6983],[-73.9933,40.6948],[-73.9887,40.7168],[-74.0177,40.7203]]]},"properties":{"units":"us","forecastGenerator":"HourlyForecastGenerator","generatedAt":"2024-01-15T12:51:31+00:00","updateTime":"2024-01-15T10:28:23+00:00","validTimes":"2024-01-15T04:00:00+00:00/P7DT21H","elevation":{"unitCode":"wmoUnit:m","value":2.1336},"periods":[{"number":1,"name":"","startTime":"2024-01-15T07:00:00-05:00","endTime":"2024-01-15T08:00:00-05:00","isDaytime":true,"temperature":29,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":2},"dewpoint":{"unitCode":"wmoUnit:degC","value":-8.3333},"relativeHumidity":{"unitCode":"wmoUnit:percent","value":55},"windSpeed":"12 mph","windDirection":"NW","icon":"https://api.weather.gov/icons/land/day/few?size=small","shortForecast":"Sunny","detailedForecast":""},{"number":2,"name":"","startTime":"2024-01-15T08:00:00-05:00","endTime":"2024-01-15T09:00:00-05:00","isDaytime":true,"temperature":30,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":2},"dewpoint":{"unitCode":"wmoUnit:degC","value":-8.3333},"relativeHumidity":{"unitCode":"wmoUnit:percent","value":53},"windSpeed":"13 mph","windDirection":"NW","icon":"https://api.weather.gov/icons/land/day/few?size=small","shortForecast":"Sunny","detailedForecast":""},{"number":3,"name":"","startTime":"2024-01-15T12:00:00-05:00","endTime":"2024-01-15T13:00:00-05:00","isDaytime":true,"temperature":34,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":15},"dewpoint":{"unitCode":"wmoUnit:degC","value":-7.7778},"relativeHumidity":{"unitCode":"wmoUnit:percent","value":47},"windSpeed":"14 mph","windDirection":"W","icon":"https://api.weather.gov/icons/land/day/sct?size=small","shortForecast":"Mostly Sunny","detailedForecast":""},{"number":4,"name":"","startTime":"2024-01-15T18:00:00-05:00","endTime":"2024-01-15T19:00:00-05:00","isDaytime":false,"temperature":31,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":40},"dewpoint":{"unitCode":"wmoUnit:degC","value":-5.5556},"relativeHumidity":{"unitCode":"wmoUnit:percent","value":63},"windSpeed":"9 mph","windDirection":"SW","icon":"https://api.weather.gov/icons/land/night/rain_showers,40?size=small","shortForecast":"Chance Rain And Snow Showers","detailedForecast":""}]}}"#;
+
+    const DAILY_RESPONSE: &str = r#"{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[-74.0177,40.7203],[-74.0223,40.6983],[-73.9933,40.6948],[-73.9887,40.7168],[-74.0177,40.7203]]]},"properties":{"units":"us","forecastGenerator":"BaselineForecastGenerator","generatedAt":"2024-01-15T12:51:31+00:00","updateTime":"2024-01-15T10:28:23+00:00","validTimes":"2024-01-15T04:00:00+00:00/P7DT21H","elevation":{"unitCode":"wmoUnit:m","value":2.1336},"periods":[{"number":1,"name":"Today","startTime":"2024-01-15T07:00:00-05:00","endTime":"2024-01-15T18:00:00-05:00","isDaytime":true,"temperature":35,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":20},"windSpeed":"10 to 14 mph","windDirection":"W","icon":"https://api.weather.gov/icons/land/day/sct?size=medium","shortForecast":"Mostly Sunny","detailedForecast":"Mostly sunny, with a high near 35."},{"number":2,"name":"Tonight","startTime":"2024-01-15T18:00:00-05:00","endTime":"2024-01-16T06:00:00-05:00","isDaytime":false,"temperature":28,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":70},"windSpeed":"6 mph","windDirection":"SW","icon":"https://api.weather.gov/icons/land/night/snow,70?size=medium","shortForecast":"Light Snow Likely","detailedForecast":"Snow likely after 7pm. Cloudy, with a low around 28."},{"number":3,"name":"Tuesday","startTime":"2024-01-16T06:00:00-05:00","endTime":"2024-01-16T18:00:00-05:00","isDaytime":true,"temperature":33,"temperatureUnit":"F","temperatureTrend":"","probabilityOfPrecipitation":{"unitCode":"wmoUnit:percent","value":60},"windSpeed":"7 mph","windDirection":"NW","icon":"https://api.weather.gov/icons/land/day/snow,60?size=medium","shortForecast":"Chance Light Snow And Areas Of Fog","detailedForecast":"A chance of light snow before 10am. Cloudy, with a high near 33."}]}}"#;
+
+    const ALERTS_RESPONSE: &str = r#"{"@context":["https://geojson.org/geojson-ld/geojson-context.jsonld",{"@version":"1.1","wx":"https://api.weather.gov/ontology#","@vocab":"https://api.weather.gov/ontology#"}],"type":"FeatureCollection","features":[{"id":"https://api.weather.gov/alerts/urn:oid:2.49.0.1.840.0.1a2b3c4d5e6f","type":"Feature","geometry":null,"properties":{"@id":"https://api.weather.gov/alerts/urn:oid:2.49.0.1.840.0.1a2b3c4d5e6f","@type":"wx:Alert","id":"urn:oid:2.49.0.1.840.0.1a2b3c4d5e6f","areaDesc":"New York (Manhattan)","affectedZones":["https://api.weather.gov/zones/forecast/NYZ072"],"sent":"2024-01-15T03:42:00-05:00","effective":"2024-01-15T03:42:00-05:00","onset":"2024-01-15T19:00:00-05:00","expires":"2024-01-15T16:00:00-05:00","ends":"2024-01-16T10:00:00-05:00","status":"Actual","messageType":"Alert","category":"Met","severity":"Moderate","certainty":"Likely","urgency":"Expected","event":"Winter Weather Advisory","sender":"w-nws.webmaster@noaa.gov","senderName":"NWS Upton NY","headline":"Winter Weather Advisory issued January 15 at 3:42AM EST until January 16 at 10:00AM EST by NWS Upton NY","description":"* WHAT...Snow expected. Total snow accumulations of 1 to 3 inches.\n\n* WHERE...New York (Manhattan).\n\n* WHEN...From 7 PM this evening to 10 AM EST Tuesday.","instruction":"Slow down and use caution while traveling.","response":"Execute"}},{"id":"https://api.weather.gov/alerts/urn:oid:2.49.0.1.840.0.6f5e4d3c2b1a","type":"Feature","geometry":null,"properties":{"@id":"https://api.weather.gov/alerts/urn:oid:2.49.0.1.840.0.6f5e4d3c2b1a","@type":"wx:Alert","id":"urn:oid:2.49.0.1.840.0.6f5e4d3c2b1a","areaDesc":"New York (Manhattan)","affectedZones":["https://api.weather.gov/zones/forecast/NYZ072"],"sent":"2024-01-15T05:10:00-05:00","effective":"2024-01-15T05:10:00-05:00","onset":null,"expires":"2024-01-15T09:00:00-05:00","ends":null,"status":"Actual","messageType":"Alert","category":"Met","severity":"Minor","certainty":"Observed","urgency":"Immediate","event":"Special Weather Statement","sender":"w-nws.webmaster@noaa.gov","senderName":"NWS Upton NY","headline":"Special Weather Statement issued January 15 at 5:10AM EST by NWS Upton NY","description":"Areas of fog will reduce visibility to one quarter mile or less at times.","instruction":null,"response":"Execute"}}],"title":"Current watches, warnings, and advisories for 40.7128 N, 74.006 W","updated":"2024-01-15T12:45:00+00:00"}"#;
+
+    #[test]
+    pub fn test_deserialize_points_response() -> Result<()> {
+        let points: PointsResponse = serde_json::from_str(POINTS_RESPONSE)?;
+
+        assert_eq!(
+            "https://api.weather.gov/gridpoints/OKX/33,35/forecast/hourly",
+            points.properties.forecast_hourly
+        );
+        assert_eq!(
+            "https://api.weather.gov/gridpoints/OKX/33,35/forecast",
+            points.properties.forecast
+        );
+        assert_eq!("America/New_York", points.properties.timezone);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_deserialize_hourly_response() -> Result<()> {
+        let response: ForecastResponse = serde_json::from_str(HOURLY_RESPONSE)?;
+        let weather = parse_hourly(response, Tz::America__New_York)?;
+
+        assert_eq!(4, weather.len());
+        assert_eq!(7, weather[0].timestamp.hour());
+        assert_eq!("Sunny", weather[0].summary);
+        assert_eq!(29.0, weather[0].temp);
+        assert!(weather[0].apparent_temp.is_none());
+        assert_eq!("Chance Rain and Snow Showers", weather[3].summary);
+        assert_eq!(Some(0.4), weather[3].precip_probability);
+        assert_eq!(Some(12.0), weather[0].wind_speed);
+        assert_eq!(Some(315.0), weather[0].wind_bearing);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_daily_response() -> Result<()> {
+        let response: ForecastResponse = serde_json::from_str(DAILY_RESPONSE)?;
+        let daily = parse_daily(response, Tz::America__New_York);
+
+        // Tuesday has no following night period, so it has no low.
+        assert_eq!(1, daily.len());
+        assert_eq!("2024-01-15", daily[0].date.to_string());
+        assert_eq!("Mostly Sunny", daily[0].summary);
+        assert_eq!(35.0, daily[0].high);
+        assert_eq!(28.0, daily[0].low);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_wind() {
+        assert_eq!(Some(12.0), parse_wind_speed("12 mph"));
+        assert_eq!(Some(15.0), parse_wind_speed("10 to 15 mph"));
+        assert_eq!(None, parse_wind_speed("calm"));
+
+        assert_eq!(Some(0.0), parse_wind_direction("N"));
+        assert_eq!(Some(247.5), parse_wind_direction("WSW"));
+        assert_eq!(None, parse_wind_direction("up"));
+    }
+
+    #[test]
+    pub fn test_deserialize_empty_hourly_response() -> Result<()> {
+        let response: ForecastResponse = serde_json::from_str(r#"{"properties":{"periods":[]}}"#)?;
+        assert!(parse_hourly(response, Tz::America__New_York).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_deserialize_alerts_response() -> Result<()> {
+        let response: AlertsResponse = serde_json::from_str(ALERTS_RESPONSE)?;
+        let alerts = parse_alerts(response, Tz::America__New_York);
+
+        assert_eq!(2, alerts.len());
+
+        assert_eq!("Winter Weather Advisory", alerts[0].event);
+        assert_eq!("NWS Upton NY", alerts[0].sender_name);
+        assert_eq!(19, alerts[0].start.hour());
+        assert_eq!(10, alerts[0].end.hour());
+        assert!(alerts[0].description.starts_with("* WHAT...Snow expected"));
+
+        // No onset/ends, so effective/expires bound the alert.
+        assert_eq!("Special Weather Statement", alerts[1].event);
+        assert_eq!(5, alerts[1].start.hour());
+        assert_eq!(9, alerts[1].end.hour());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_deserialize_no_alerts_response() -> Result<()> {
+        let response: AlertsResponse =
+            serde_json::from_str(r#"{"type":"FeatureCollection","features":[]}"#)?;
+        assert!(parse_alerts(response, Tz::America__New_York).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_failed_alerts_leave_no_alerts() {
+        let tz = Tz::America__New_York;
+        let alerts = |response: &str| {
+            let response =
+                serde_json::from_str::<AlertsResponse>(response).map_err(anyhow::Error::from);
+            alerts_or_empty(response.map(|alerts| parse_alerts(alerts, tz)))
+        };
+
+        assert_eq!(2, alerts(ALERTS_RESPONSE).len());
+        assert!(alerts("<html>503 Service Unavailable</html>").is_empty());
+        assert!(alerts_or_empty(Err(anyhow!("503 Service Unavailable"))).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_http_get_sends_user_agent() -> Result<()> {
+        let app = Router::new().route(
+            "/points",
+            get(|headers: HeaderMap| async move {
+                headers["user-agent"]
+                    .to_str()
+                    .unwrap_or_default()
+                    .to_string()
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/points", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let user_agent = http_get(&url, &[]).await?;
+        assert_eq!(USER_AGENT, user_agent);
+        assert!(user_agent.starts_with("jakesky/"), "{user_agent}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+use crate::weather::{
+    DailyWeather, FetchOptions, ForecastFuture, ForecastSource, Location, Units, Weather,
+    WeatherForecast,
+};
+use anyhow::{Context, Result, anyhow};
+use chrono::serde::ts_seconds;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use jluszcz_rust_utils::cache::{CacheMode, dated_cache_path, try_cached_query_json};
+use jluszcz_rust_utils::query;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Variables requested for both the current conditions and the hourly series.
+const VARIABLES: &str = "temperature_2m,apparent_temperature,weather_code,wind_speed_10m,wind_gusts_10m,wind_direction_10m";
+
+/// Precipitation forecasts are only available for the hourly series.
+const HOURLY_VARIABLES: &str = "temperature_2m,apparent_temperature,weather_code,wind_speed_10m,wind_gusts_10m,wind_direction_10m,precipitation_probability,precipitation";
+
+/// Variables requested for the daily series.
+const DAILY_VARIABLES: &str = "temperature_2m_max,temperature_2m_min,weather_code";
+
+#[derive(Deserialize, Debug)]
+struct Response {
+    timezone: String,
+    current: Current,
+    hourly: Hourly,
+    daily: Daily,
+}
+
+#[derive(Deserialize, Debug)]
+struct Current {
+    #[serde(with = "ts_seconds")]
+    time: DateTime<Utc>,
+
+    #[serde(alias = "temperature_2m")]
+    temp: f64,
+
+    #[serde(alias = "apparent_temperature", default)]
+    apparent_temp: Option<f64>,
+
+    #[serde(alias = "weathercode")]
+    weather_code: u8,
+
+    #[serde(alias = "wind_speed_10m", default)]
+    wind_speed: Option<f64>,
+
+    #[serde(alias = "wind_gusts_10m", default)]
+    wind_gust: Option<f64>,
+
+    #[serde(alias = "wind_direction_10m", default)]
+    wind_bearing: Option<f64>,
+}
+
+/// Open-Meteo returns the hourly series as parallel arrays, one per variable.
+#[derive(Deserialize, Debug)]
+struct Hourly {
+    time: Vec<i64>,
+
+    #[serde(alias = "temperature_2m")]
+    temp: Vec<f64>,
+
+    #[serde(alias = "apparent_temperature", default)]
+    apparent_temp: Vec<Option<f64>>,
+
+    #[serde(alias = "weathercode")]
+    weather_code: Vec<u8>,
+
+    /// Percent chance, from 0 to 100.
+    #[serde(alias = "precipitation_probability", default)]
+    precip_probability: Vec<Option<f64>>,
+
+    /// Millimeters over the preceding hour.
+    #[serde(alias = "precipitation", default)]
+    precip_amount: Vec<Option<f64>>,
+
+    #[serde(alias = "wind_speed_10m", default)]
+    wind_speed: Vec<Option<f64>>,
+
+    #[serde(alias = "wind_gusts_10m", default)]
+    wind_gust: Vec<Option<f64>>,
+
+    #[serde(alias = "wind_direction_10m", default)]
+    wind_bearing: Vec<Option<f64>>,
+}
+
+/// Parallel arrays, like [`Hourly`]; each time is local midnight.
+#[derive(Deserialize, Debug)]
+struct Daily {
+    time: Vec<i64>,
+
+    #[serde(alias = "temperature_2m_max")]
+    high: Vec<f64>,
+
+    #[serde(alias = "temperature_2m_min")]
+    low: Vec<f64>,
+
+    #[serde(alias = "weathercode")]
+    weather_code: Vec<u8>,
+}
+
+/// Speakable summary for a WMO weather interpretation code, as documented at
+/// <https://open-meteo.com/en/docs>.
+fn normalize_weather(code: u8) -> Result<&'static str> {
+    Ok(match code {
+        0 => "Clear",
+        1 => "Mostly Clear",
+        2 => "Partly Cloudy",
+        3 => "Cloudy",
+        45 => "Fog",
+        48 => "Freezing Fog",
+        51 => "Light Drizzle",
+        53 => "Drizzle",
+        55 => "Heavy Drizzle",
+        56 | 57 => "Freezing Drizzle",
+        61 => "Light Rain",
+        63 => "Rain",
+        65 => "Heavy Rain",
+        66 | 67 => "Freezing Rain",
+        71 => "Light Snow",
+        73 => "Snow",
+        75 => "Heavy Snow",
+        77 => "Snow Grains",
+        80 => "Light Showers",
+        81 => "Showers",
+        82 => "Heavy Showers",
+        85 => "Snow Showers",
+        86 => "Heavy Snow Showers",
+        95 => "Thunderstorms",
+        96 | 99 => "Thunderstorms with Hail",
+        _ => return Err(anyhow!("Unknown WMO weather code: {code}")),
+    })
+}
+
+fn current_to_weather(current: &Current, timezone: Tz) -> Result<Weather> {
+    Ok(Weather {
+        timestamp: current.time.with_timezone(&timezone),
+        summary: normalize_weather(current.weather_code)?.to_string(),
+        units: Units::Imperial,
+        temp: current.temp,
+        apparent_temp: current.apparent_temp,
+        precip_probability: None,
+        precip_amount: None,
+        wind_speed: current.wind_speed,
+        wind_gust: current.wind_gust,
+        wind_bearing: current.wind_bearing,
+        spread: None,
+    })
+}
+
+fn hourly_to_weather(hourly: &Hourly, index: usize, timezone: Tz) -> Result<Weather> {
+    let timestamp = DateTime::from_timestamp(hourly.time[index], 0)
+        .ok_or_else(|| anyhow!("Invalid timestamp {}", hourly.time[index]))?;
+
+    Ok(Weather {
+        timestamp: timestamp.with_timezone(&timezone),
+        summary: normalize_weather(hourly.weather_code[index])?.to_string(),
+        units: Units::Imperial,
+        temp: hourly.temp[index],
+        apparent_temp: hourly.apparent_temp.get(index).copied().flatten(),
+        precip_probability: hourly
+            .precip_probability
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|p| p / 100.0),
+        precip_amount: hourly.precip_amount.get(index).copied().flatten(),
+        wind_speed: hourly.wind_speed.get(index).copied().flatten(),
+        wind_gust: hourly.wind_gust.get(index).copied().flatten(),
+        wind_bearing: hourly.wind_bearing.get(index).copied().flatten(),
+        spread: None,
+    })
+}
+
+fn parse_hourly(hourly: &Hourly, timezone: Tz) -> Result<Vec<Weather>> {
+    let len = hourly.time.len();
+    if hourly.temp.len() != len || hourly.weather_code.len() != len {
+        return Err(anyhow!(
+            "Open-Meteo hourly arrays have mismatched lengths: time={len}, temperature_2m={}, weather_code={}",
+            hourly.temp.len(),
+            hourly.weather_code.len()
+        ));
+    }
+
+    (0..len)
+        .map(|index| {
+            hourly_to_weather(hourly, index, timezone).with_context(|| {
+                format!("Failed to parse hourly weather entry {index} from Open-Meteo")
+            })
+        })
+        .collect()
+}
+
+fn parse_daily(daily: &Daily, timezone: Tz) -> Result<Vec<DailyWeather>> {
+    let len = daily.time.len();
+    if daily.high.len() != len || daily.low.len() != len || daily.weather_code.len() != len {
+        return Err(anyhow!(
+            "Open-Meteo daily arrays have mismatched lengths: time={len}, temperature_2m_max={}, temperature_2m_min={}, weather_code={}",
+            daily.high.len(),
+            daily.low.len(),
+            daily.weather_code.len()
+        ));
+    }
+
+    (0..len)
+        .map(|index| {
+            let date = DateTime::from_timestamp(daily.time[index], 0)
+                .ok_or_else(|| anyhow!("Invalid timestamp {}", daily.time[index]))?;
+
+            Ok(DailyWeather {
+                date: date.with_timezone(&timezone).date_naive(),
+                summary: normalize_weather(daily.weather_code[index])?.to_string(),
+                units: Units::Imperial,
+                high: daily.high[index],
+                low: daily.low[index],
+            })
+        })
+        .collect::<Result<_>>()
+        .with_context(|| "Failed to parse daily weather from Open-Meteo")
+}
+
+pub async fn get_weather(
+    cache_mode: CacheMode,
+    latitude: f64,
+    longitude: f64,
+) -> Result<WeatherForecast> {
+    let cache_path = dated_cache_path(&format!("openmeteo_{latitude:.1}_{longitude:.1}"));
+
+    let response: Response = try_cached_query_json(cache_mode, &cache_path, || {
+        query(latitude, longitude)
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get weather data from Open-Meteo for coordinates {latitude}, {longitude}"
+        )
+    })?;
+
+    let timezone = Tz::from_str(&response.timezone).with_context(|| {
+        format!(
+            "Failed to parse timezone '{}' from Open-Meteo API",
+            response.timezone
+        )
+    })?;
+
+    let current = current_to_weather(&response.current, timezone)
+        .with_context(|| "Failed to parse current weather from Open-Meteo")?;
+    let upcoming = parse_hourly(&response.hourly, timezone)
+        .with_context(|| "Failed to parse Open-Meteo API response")?;
+    let daily = parse_daily(&response.daily, timezone)?;
+
+    Ok(WeatherForecast {
+        timezone,
+        current,
+        upcoming,
+        daily,
+        alerts: Vec::new(), // Open-Meteo does not publish weather alerts
+    })
+}
+
+pub struct OpenMeteo;
+
+impl ForecastSource for OpenMeteo {
+    fn id(&self) -> &'static str {
+        "openmeteo"
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        location: &'a Location,
+        options: &'a FetchOptions,
+    ) -> ForecastFuture<'a> {
+        Box::pin(async move {
+            get_weather(options.cache_mode, location.latitude, location.longitude).await
+        })
+    }
+}
+
+async fn query(latitude: f64, longitude: f64) -> Result<String> {
+    let lat = latitude.to_string();
+    let lon = longitude.to_string();
+    query::http_get(
+        "https://api.open-meteo.com/v1/forecast",
+        &[
+            ("current", VARIABLES),
+            ("hourly", HOURLY_VARIABLES),
+            ("daily", DAILY_VARIABLES),
+            ("temperature_unit", "fahrenheit"),
+            ("wind_speed_unit", "mph"),
+            ("timezone", "auto"),
+            ("timeformat", "unixtime"),
+            ("forecast_days", "7"),
+            ("latitude", &lat),
+            ("longitude", &lon),
+        ],
+    )
+    .await
+    .with_context(|| "Failed to make HTTP request to Open-Meteo API")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Timelike;
+
+    const EXAMPLE_API_RESPONSE: &str = r#"{"latitude":40.710335,"longitude":-73.99307,"generationtime_ms":0.0820159912109375,"utc_offset_seconds":-18000,"timezone":"America/New_York","timezone_abbreviation":"EST","elevation":32.0,"current_units":{"time":"unixtime","interval":"seconds","temperature_2m":"°F","apparent_temperature":"°F","weather_code":"wmo code","wind_speed_10m":"mp/h","wind_gusts_10m":"mp/h","wind_direction_10m":"°"},"current":{"time":1705320000,"interval":900,"temperature_2m":29.1,"apparent_temperature":20.3,"weather_code":1,"wind_speed_10m":11.2,"wind_gusts_10m":24.8,"wind_direction_10m":290},"hourly_units":{"time":"unixtime","temperature_2m":"°F","apparent_temperature":"°F","weather_code":"wmo code","precipitation_probability":"%","precipitation":"mm","wind_speed_10m":"mp/h","wind_gusts_10m":"mp/h","wind_direction_10m":"°"},"hourly":{"time":[1705294800,1705298400,1705302000,1705305600,1705309200,1705312800,1705316400,1705320000,1705323600,1705327200,1705330800,1705334400],"temperature_2m":[33.4,32.9,32.2,31.6,31.1,30.4,29.8,29.1,29.6,30.8,32.1,33.5],"apparent_temperature":[25.1,24.6,23.8,23.0,22.4,21.5,20.9,20.3,21.0,22.6,24.2,25.9],"weather_code":[3,3,2,2,1,1,0,1,2,61,63,95],"precipitation_probability":[5,5,3,2,0,0,0,0,10,45,70,85],"precipitation":[0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.4,1.8,4.2],"wind_speed_10m":[8.1,8.4,9.0,9.6,10.2,10.5,10.9,11.2,12.0,14.5,19.8,23.6],"wind_gusts_10m":[17.4,18.1,19.2,20.6,21.9,22.8,23.7,24.8,26.4,31.1,38.2,44.7],"wind_direction_10m":[280,282,284,285,287,288,289,290,292,250,235,230]},"daily_units":{"time":"unixtime","temperature_2m_max":"°F","temperature_2m_min":"°F","weather_code":"wmo code"},"daily":{"time":[1705294800,1705381200],"temperature_2m_max":[36.4,33.8],"temperature_2m_min":[28.9,25.2],"weather_code":[63,71]}}"#;
+
+    #[test]
+    fn test_deserialize() -> Result<()> {
+        let response: Response = serde_json::from_str(EXAMPLE_API_RESPONSE)?;
+        let timezone = Tz::from_str(&response.timezone)?;
+
+        let current = current_to_weather(&response.current, timezone)?;
+        assert_eq!("Mostly Clear", current.summary);
+        assert_eq!(29.1, current.temp);
+        assert_eq!(Some(20.3), current.apparent_temp);
+        assert_eq!(Some(11.2), current.wind_speed);
+        assert_eq!(Some(24.8), current.wind_gust);
+        assert_eq!(Some(290.0), current.wind_bearing);
+
+        let hourly = parse_hourly(&response.hourly, timezone)?;
+        assert_eq!(12, hourly.len());
+        assert_eq!(0, hourly[0].timestamp.hour());
+        assert_eq!("Cloudy", hourly[0].summary);
+        assert_eq!(Some(25.1), hourly[0].apparent_temp);
+        assert_eq!("Light Rain", hourly[9].summary);
+        assert_eq!(Some(0.45), hourly[9].precip_probability);
+        assert_eq!(Some(0.4), hourly[9].precip_amount);
+        assert_eq!(Some(44.7), hourly[11].wind_gust);
+
+        let daily = parse_daily(&response.daily, timezone)?;
+        assert_eq!(2, daily.len());
+        assert_eq!("2024-01-15", daily[0].date.to_string());
+        assert_eq!("Rain", daily[0].summary);
+        assert_eq!(36.4, daily[0].high);
+        assert_eq!(28.9, daily[0].low);
+        assert_eq!("Light Snow", daily[1].summary);
+        assert_eq!("Thunderstorms", hourly[11].summary);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_hourly_mismatched_lengths() {
+        let hourly = Hourly {
+            time: vec![1705294800, 1705298400],
+            temp: vec![33.4],
+            apparent_temp: Vec::new(),
+            weather_code: vec![3, 3],
+            precip_probability: Vec::new(),
+            precip_amount: Vec::new(),
+            wind_speed: Vec::new(),
+            wind_gust: Vec::new(),
+            wind_bearing: Vec::new(),
+        };
+
+        assert!(parse_hourly(&hourly, Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn test_normalize_weather() {
+        assert_eq!("Clear", normalize_weather(0).unwrap());
+        assert_eq!("Cloudy", normalize_weather(3).unwrap());
+        assert_eq!("Heavy Snow", normalize_weather(75).unwrap());
+        assert_eq!("Thunderstorms with Hail", normalize_weather(99).unwrap());
+        assert!(normalize_weather(42).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! The JSON form of a [`WeatherReport`], for consumers that want the
+//! normalized data rather than speech.
+//!
+//! A serialized report carries a top-level `version`, which is bumped on any
+//! change that would break an existing reader (renaming, removing or
+//! retyping a field). New fields may appear within a version, so readers
+//! should ignore ones they don't recognize.
+
+use crate::weather::{DailyWeather, Weather, WeatherAlert, WeatherReport};
+use anyhow::{Error, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// Current version of the report schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A report as it's written out, tagged with the schema version.
+#[derive(Serialize, Deserialize)]
+pub(super) struct ReportDocument {
+    version: u32,
+    weather: Vec<Weather>,
+    #[serde(default)]
+    hourly: Vec<Weather>,
+    #[serde(default)]
+    daily: Vec<DailyWeather>,
+    #[serde(default)]
+    alerts: Vec<WeatherAlert>,
+}
+
+impl From<WeatherReport> for ReportDocument {
+    fn from(report: WeatherReport) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            weather: report.weather,
+            hourly: report.hourly,
+            daily: report.daily,
+            alerts: report.alerts,
+        }
+    }
+}
+
+impl TryFrom<ReportDocument> for WeatherReport {
+    type Error = Error;
+
+    fn try_from(document: ReportDocument) -> Result<Self, Self::Error> {
+        if document.version != SCHEMA_VERSION {
+            return Err(anyhow!(
+                "Unsupported report schema version {} (expected {SCHEMA_VERSION})",
+                document.version
+            ));
+        }
+
+        Ok(Self {
+            weather: document.weather,
+            hourly: document.hourly,
+            daily: document.daily,
+            alerts: document.alerts,
+        })
+    }
+}
+
+/// (De)serializes a `DateTime<Tz>` as RFC 3339 followed by the IANA zone in
+/// brackets (RFC 9557), e.g. `2024-06-01T08:00:00-04:00[America/New_York]`,
+/// so the zone survives a round trip. A timestamp without a zone is read as
+/// UTC.
+pub(super) mod timestamp {
+    use chrono::DateTime;
+    use chrono_tz::Tz;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(timestamp: &DateTime<Tz>, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(&format_args!(
+            "{}[{}]",
+            timestamp.to_rfc3339(),
+            timestamp.timezone().name()
+        ))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Tz>, D::Error> {
+        let s = String::deserialize(d)?;
+
+        let (rfc3339, tz) = match s.strip_suffix(']').and_then(|s| s.split_once('[')) {
+            Some((rfc3339, name)) => (rfc3339, name.parse::<Tz>().map_err(D::Error::custom)?),
+            None => (s.as_str(), Tz::UTC),
+        };
+
+        DateTime::parse_from_rfc3339(rfc3339)
+            .map(|timestamp| timestamp.with_timezone(&tz))
+            .map_err(|e| D::Error::custom(format!("Invalid timestamp {s:?}: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::weather::Units;
+    use chrono::{NaiveDate, TimeZone};
+    use chrono_tz::Tz;
+    use serde_json::json;
+
+    fn report() -> WeatherReport {
+        let timestamp = Tz::America__New_York
+            .with_ymd_and_hms(2024, 6, 1, 8, 0, 0)
+            .unwrap();
+
+        WeatherReport {
+            weather: vec![Weather {
+                timestamp,
+                ..Weather::test(Some("Clear"))
+            }],
+            hourly: Vec::new(),
+            daily: vec![DailyWeather {
+                date: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+                summary: "Clear".to_string(),
+                units: Units::Imperial,
+                high: 78.0,
+                low: 61.0,
+            }],
+            alerts: vec![WeatherAlert {
+                event: "Heat Advisory".to_string(),
+                sender_name: "NWS New York NY".to_string(),
+                start: timestamp,
+                end: timestamp + chrono::Duration::hours(10),
+                description: "Hot.".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_serialize_report() {
+        let value = serde_json::to_value(report()).unwrap();
+
+        assert_eq!(json!(SCHEMA_VERSION), value["version"]);
+        assert_eq!(
+            json!("2024-06-01T08:00:00-04:00[America/New_York]"),
+            value["weather"][0]["timestamp"]
+        );
+        assert_eq!(json!("imperial"), value["weather"][0]["units"]);
+        assert_eq!(json!("2024-06-01"), value["daily"][0]["date"]);
+        assert_eq!(
+            json!("2024-06-01T18:00:00-04:00[America/New_York]"),
+            value["alerts"][0]["end"]
+        );
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let json = serde_json::to_string(&report()).unwrap();
+        let parsed: WeatherReport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+        assert_eq!(
+            Tz::America__New_York,
+            parsed.weather[0].timestamp.timezone()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_other_versions() {
+        let json = r#"{"version": 2, "weather": []}"#;
+
+        let e = serde_json::from_str::<WeatherReport>(json).unwrap_err();
+        assert!(e.to_string().contains("schema version 2"), "{e}");
+    }
+
+    #[test]
+    fn test_deserialize_timestamp_without_zone() {
+        let json = r#"{
+            "version": 1,
+            "weather": [{
+                "timestamp": "2024-06-01T12:00:00Z",
+                "summary": "Clear",
+                "units": "metric",
+                "temp": 25.0
+            }]
+        }"#;
+
+        let report: WeatherReport = serde_json::from_str(json).unwrap();
+        let weather = &report.weather[0];
+        assert_eq!(Tz::UTC, weather.timestamp.timezone());
+        assert_eq!(Units::Metric, weather.units);
+        assert_eq!(None, weather.precip_probability);
+        assert!(report.daily.is_empty());
+    }
+}
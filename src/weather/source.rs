@@ -0,0 +1,121 @@
+//! Pluggable forecast sources and the registry that `WeatherProvider` looks
+//! them up in.
+
+use crate::weather::accu_weather::AccuWeather;
+use crate::weather::nws::Nws;
+use crate::weather::open_meteo::OpenMeteo;
+use crate::weather::open_weather::OpenWeather;
+use crate::weather::{ApiKey, WeatherForecast};
+use anyhow::{Result, anyhow};
+use jluszcz_rust_utils::cache::CacheMode;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+/// Coordinates to fetch a forecast for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Location {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+/// Settings shared by every source for a single fetch.
+#[derive(Clone)]
+pub struct FetchOptions {
+    pub cache_mode: CacheMode,
+    pub api_key: Option<ApiKey>,
+}
+
+impl FetchOptions {
+    pub fn new(cache_mode: CacheMode, api_key: Option<ApiKey>) -> Self {
+        Self {
+            cache_mode,
+            api_key,
+        }
+    }
+
+    /// The configured API key, or an error naming the source that needs it.
+    pub fn require_api_key(&self, id: &str) -> Result<&ApiKey> {
+        self.api_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("The {id} provider requires an API key"))
+    }
+}
+
+pub type ForecastFuture<'a> = Pin<Box<dyn Future<Output = Result<WeatherForecast>> + Send + 'a>>;
+
+/// A weather service that can produce a [`WeatherForecast`] for a location.
+///
+/// Implementations are registered with [`register_source`] and selected by
+/// [`ForecastSource::id`]. `fetch` returns a boxed future so sources can be
+/// stored as trait objects.
+pub trait ForecastSource: Send + Sync {
+    /// Identifier used to select this source, e.g. on the command line.
+    fn id(&self) -> &'static str;
+
+    /// Whether [`FetchOptions::api_key`] must be set for `fetch` to succeed.
+    fn requires_api_key(&self) -> bool;
+
+    fn fetch<'a>(&'a self, location: &'a Location, options: &'a FetchOptions)
+    -> ForecastFuture<'a>;
+}
+
+static REGISTRY: LazyLock<RwLock<Vec<Arc<dyn ForecastSource>>>> = LazyLock::new(|| {
+    RwLock::new(vec![
+        Arc::new(AccuWeather),
+        Arc::new(Nws),
+        Arc::new(OpenMeteo),
+        Arc::new(OpenWeather),
+    ])
+});
+
+/// Adds a source to the registry so it can be selected by id. Call this
+/// before parsing arguments so the CLI lists it.
+///
+/// Ids are matched case-insensitively; registering a duplicate id fails.
+pub fn register_source(source: impl ForecastSource + 'static) -> Result<()> {
+    let mut sources = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+
+    if sources
+        .iter()
+        .any(|s| s.id().eq_ignore_ascii_case(source.id()))
+    {
+        return Err(anyhow!(
+            "A weather provider with id {} is already registered",
+            source.id()
+        ));
+    }
+
+    sources.push(Arc::new(source));
+    Ok(())
+}
+
+/// Ids of all registered sources, sorted.
+pub fn registered_ids() -> Vec<&'static str> {
+    let mut ids: Vec<_> = REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|s| s.id())
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+pub(crate) fn find_source(id: &str) -> Option<Arc<dyn ForecastSource>> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|s| s.id().eq_ignore_ascii_case(id))
+        .cloned()
+}